@@ -0,0 +1,22 @@
+use smart_account_interfaces::PluginInstallParams;
+use soroban_sdk::{xdr::ToXdr, Bytes, BytesN, Env, Val, Vec};
+
+/// Domain tag prefixed to the configuration when deriving account salts.
+pub const ACCOUNT_SALT_TAG: &str = "smart-account";
+
+/// Salt of the account deployed with the given initial configuration.
+///
+/// The salt is the SHA-256 of `ACCOUNT_SALT_TAG` followed by the XDR encoding of the
+/// `(signers, plugins)` tuple, so the account address depends only on the factory and the
+/// configuration, and wallets can derive it off-chain. The tag keeps account salts apart
+/// from the salts deployers pick for `deploy` and `deploy_idempotent`. The order of
+/// signers and plugins matters.
+pub fn account_salt(
+    env: &Env,
+    signers: &Vec<Val>,
+    plugins: &Vec<PluginInstallParams>,
+) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, ACCOUNT_SALT_TAG.as_bytes());
+    preimage.append(&(signers.clone(), plugins.clone()).to_xdr(env));
+    env.crypto().sha256(&preimage).into()
+}
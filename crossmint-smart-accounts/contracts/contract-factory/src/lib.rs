@@ -1,13 +1,31 @@
 #![no_std]
+use registry::Deployment;
+use release::{
+    FactoryError, Release, ReleasePublishedEvent, ReleaseStatus, ReleaseStatusChangedEvent,
+};
+use smart_account_interfaces::{PluginInstallParams, ReleaseRegistry};
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, vec, Address, Bytes, BytesN, Env, Symbol,
-    Val, Vec,
+    contract, contractimpl, contracttype, panic_with_error, symbol_short, vec, Address, Bytes,
+    BytesN, Env, IntoVal, String, Symbol, Val, Vec,
 };
-use stellar_access_control::{grant_role_no_auth, set_admin, AccessControl};
+use stellar_access_control::{grant_role_no_auth, has_role, set_admin, AccessControl};
 use stellar_access_control_macros::only_role;
 use stellar_default_impl_macro::default_impl;
 
+pub mod account;
+pub mod registry;
+pub mod release;
+
 const DEPLOYED_CONTRACT: Symbol = symbol_short!("DEPLOYED");
+const BATCH_DEPLOYED: Symbol = symbol_short!("BATCH");
+
+/// Maximum number of deployments in a single `deploy_batch` call.
+pub const MAX_BATCH_SIZE: u32 = 25;
+const RELEASE_PUBLISHED: Symbol = symbol_short!("PUBLISHED");
+const RELEASE_STATUS: Symbol = symbol_short!("REL_STAT");
+const UPLOADER_ROLE: Symbol = symbol_short!("uploader");
+/// Release channel `deploy_account` deploys accounts from
+pub const ACCOUNT_CHANNEL: &str = "smart-account";
 
 #[contract]
 pub struct ContractFactory;
@@ -34,6 +52,46 @@ pub struct ContractDeployedEvent {
     contract_id: Address,
 }
 
+/// Whether an idempotent deployment created the contract or found an existing one, or why
+/// a `deploy_batch` item was skipped.
+///
+/// Soroban SDK 22 gives contracts no way to read the executable of another contract, so an
+/// existing contract is compared with the requested wasm hash through the factory's
+/// registry, which holds the wasm each contract was deployed with, and not through the
+/// code actually running at its address. A contract upgraded since its deployment is
+/// still reported as `AlreadyDeployedSame`: callers relying on its current code must read
+/// the contract instance entry off-chain.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeploymentStatus {
+    NewlyDeployed,
+    /// The factory deployed the contract with the requested wasm
+    AlreadyDeployedSame,
+    /// The factory deployed other wasm at the address. Only reported by `deploy_batch`.
+    DeployedWithDifferentWasm,
+    /// A contract the factory did not record exists at the address. Only reported by
+    /// `deploy_batch`.
+    DeploymentUnverified,
+    /// The requested wasm is that of a revoked release. Only reported by `deploy_batch`.
+    ReleaseRevoked,
+}
+
+/// Outcome of `deploy_idempotent` or of one item of a `deploy_batch` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeploymentResult {
+    pub contract_id: Address,
+    pub status: DeploymentStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchDeployedEvent {
+    deployed: u32,
+    already_deployed: u32,
+    failed: u32,
+}
+
 #[contractimpl]
 impl ContractFactory {
     /// Construct the deployer with a given admin address.
@@ -44,7 +102,8 @@ impl ContractFactory {
 
     /// Deploys the contract on behalf of the `ContractFactory` contract.
     ///
-    /// This has to be authorized by an address with the `deployer` role.
+    /// Wasm of revoked releases cannot be deployed. This has to be authorized by an address
+    /// with the `deployer` role.
     #[only_role(caller, "deployer")]
     pub fn deploy(env: &Env, caller: Address, deployment_args: ContractDeploymentArgs) -> Address {
         // Deploy the contract using the uploaded Wasm with given hash on behalf
@@ -61,87 +120,139 @@ impl ContractFactory {
             constructor_args,
         } = deployment_args;
 
-        let contract_id = env
-            .deployer()
-            .with_current_contract(salt)
-            .deploy_v2(wasm_hash, constructor_args);
-
-        env.events().publish(
-            vec![env, DEPLOYED_CONTRACT],
-            vec![
-                env,
-                ContractDeployedEvent {
-                    contract_id: contract_id.clone(),
-                },
-            ],
-        );
-        contract_id
+        deploy_contract(env, Some(caller), wasm_hash, salt, constructor_args)
     }
 
-    /// Deploys the contract on behalf of the `ContractFactory` contract.
+    /// Deploys the contract on behalf of the `ContractFactory` contract, unless a contract
+    /// already exists at its address.
     ///
-    /// This has to be authorized by an address with the `deployer` role.
+    /// The result tells whether the contract was deployed, or whether the factory already
+    /// deployed it with the requested wasm, see `DeploymentStatus`. Fails with
+    /// `DeployedWithDifferentWasm` if the factory deployed other wasm there, and with
+    /// `DeploymentUnverified` if a contract the factory did not record exists there. Wasm
+    /// of revoked releases cannot be deployed. This has to be authorized by an address with
+    /// the `deployer` role.
     #[only_role(caller, "deployer")]
     pub fn deploy_idempotent(
         env: &Env,
         caller: Address,
         deployment_args: ContractDeploymentArgs,
-    ) -> Address {
+    ) -> DeploymentResult {
         let ContractDeploymentArgs {
             wasm_hash,
             salt,
             constructor_args,
         } = deployment_args;
 
-        let tentative_contract_id = Self::get_deployed_address(env, salt.clone());
-        let is_deployed = env
-            .try_invoke_contract::<bool, soroban_sdk::Error>(
-                &tentative_contract_id,
-                &Symbol::new(env, "is_deployed"),
-                Vec::new(env),
-            )
-            .is_ok();
+        let contract_id = Self::get_deployed_address(env, salt.clone());
+        let status = if already_deployed(env, &contract_id, &wasm_hash) {
+            DeploymentStatus::AlreadyDeployedSame
+        } else {
+            deploy_contract(env, Some(caller), wasm_hash, salt, constructor_args);
+            DeploymentStatus::NewlyDeployed
+        };
+        DeploymentResult {
+            contract_id,
+            status,
+        }
+    }
 
-        if is_deployed {
-            return tentative_contract_id;
+    /// Deploys several contracts on behalf of the `ContractFactory` contract, in order.
+    ///
+    /// Each item behaves like `deploy_idempotent`, except that its failures are reported
+    /// in its result instead of failing the batch: an item the factory already deployed
+    /// with the requested wasm is reported as `AlreadyDeployedSame`, and an item finding
+    /// other or unverifiable code at its address, or deploying the wasm of a revoked
+    /// release, is skipped and reported with the matching `DeploymentStatus`. If `atomic`
+    /// is set, any of these fails the whole batch instead, existing contracts with
+    /// `AlreadyDeployed` and the others with the error of `deploy_idempotent`. A
+    /// constructor that fails always reverts the whole batch, as Soroban cannot recover
+    /// from a failed deployment.
+    ///
+    /// Publishes a `ContractDeployedEvent` per new contract and a `BatchDeployedEvent` for
+    /// the batch. This has to be authorized by an address with the `deployer` role.
+    #[only_role(caller, "deployer")]
+    pub fn deploy_batch(
+        env: &Env,
+        caller: Address,
+        deployments: Vec<ContractDeploymentArgs>,
+        atomic: bool,
+    ) -> Vec<DeploymentResult> {
+        if deployments.len() > MAX_BATCH_SIZE {
+            panic_with_error!(env, FactoryError::BatchTooLarge);
         }
 
-        let contract_id = env
-            .deployer()
-            .with_current_contract(salt)
-            .deploy_v2(wasm_hash, constructor_args);
+        let mut results = Vec::new(env);
+        let mut deployed = 0;
+        let mut existing = 0;
+        for ContractDeploymentArgs {
+            wasm_hash,
+            salt,
+            constructor_args,
+        } in deployments.iter()
+        {
+            let contract_id = Self::get_deployed_address(env, salt.clone());
+            let check = check_deployable(env, &wasm_hash)
+                .and_then(|()| existing_deployment(env, &contract_id, &wasm_hash));
+            let status = match check {
+                Ok(true) if atomic => panic_with_error!(env, FactoryError::AlreadyDeployed),
+                Ok(true) => {
+                    existing += 1;
+                    DeploymentStatus::AlreadyDeployedSame
+                }
+                Ok(false) => {
+                    deploy_contract(env, Some(caller.clone()), wasm_hash, salt, constructor_args);
+                    deployed += 1;
+                    DeploymentStatus::NewlyDeployed
+                }
+                Err(err) if atomic => panic_with_error!(env, err),
+                Err(err) => failure_status(env, err),
+            };
+            results.push_back(DeploymentResult {
+                contract_id,
+                status,
+            });
+        }
 
         env.events().publish(
-            vec![env, DEPLOYED_CONTRACT],
-            vec![
-                env,
-                ContractDeployedEvent {
-                    contract_id: contract_id.clone(),
-                },
-            ],
+            vec![env, BATCH_DEPLOYED],
+            BatchDeployedEvent {
+                deployed,
+                already_deployed: existing,
+                failed: results.len() - deployed - existing,
+            },
         );
-        contract_id
+        results
+    }
+
+    /// Uploads a contract WASM and returns its hash.
+    ///
+    /// This has to be authorized by an address with the `uploader` role. Uploading the
+    /// same WASM again returns the same hash.
+    #[only_role(caller, "uploader")]
+    pub fn upload_wasm(env: &Env, caller: Address, wasm_bytes: Bytes) -> BytesN<32> {
+        env.deployer().upload_contract_wasm(wasm_bytes)
     }
 
-    /// Uploads the contract WASM and deploys it on behalf of the `ContractFactory` contract.
+    /// Uploads the contract WASM and deploys it on behalf of the `ContractFactory` contract
+    /// using that hash, like `deploy`.
     ///
-    /// using that hash. This has to be authorized by an address with the `deployer` role.
+    /// This has to be authorized by an address with both the `deployer` and `uploader`
+    /// roles. For idempotent deployments, use `upload_wasm` then `deploy_idempotent`.
     #[only_role(caller, "deployer")]
     pub fn upload_and_deploy(
-        env: Env,
+        env: &Env,
         caller: Address,
         wasm_bytes: Bytes,
         salt: BytesN<32>,
         constructor_args: Vec<Val>,
     ) -> Address {
+        if has_role(env, &caller, &UPLOADER_ROLE).is_none() {
+            panic_with_error!(env, FactoryError::UploaderRoleRequired);
+        }
         let wasm_hash = env.deployer().upload_contract_wasm(wasm_bytes);
 
-        // Deploy the contract using the uploaded WASM hash on behalf
-        // of the current contract.
-
-        env.deployer()
-            .with_address(env.current_contract_address(), salt)
-            .deploy_v2(wasm_hash, constructor_args)
+        deploy_contract(env, Some(caller), wasm_hash, salt, constructor_args)
     }
 
     pub fn get_deployed_address(env: &Env, salt: BytesN<32>) -> Address {
@@ -149,6 +260,268 @@ impl ContractFactory {
             .with_current_contract(salt)
             .deployed_address()
     }
+
+    /// Deploys the release published as `name@version` on behalf of the `ContractFactory`
+    /// contract.
+    ///
+    /// Revoked releases cannot be deployed. This has to be authorized by an address with
+    /// the `deployer` role.
+    #[only_role(caller, "deployer")]
+    pub fn deploy_release(
+        env: &Env,
+        caller: Address,
+        name: String,
+        version: String,
+        salt: BytesN<32>,
+        constructor_args: Vec<Val>,
+    ) -> Address {
+        let release = release::get_release_by_version(env, &name, &version)
+            .unwrap_or_else(|| panic_with_error!(env, FactoryError::ReleaseNotFound));
+
+        deploy_contract(env, Some(caller), release.wasm_hash, salt, constructor_args)
+    }
+
+    /// Deploys a smart account with the given initial signers and plugins, using the
+    /// latest active release of the `ACCOUNT_CHANNEL` channel.
+    ///
+    /// The salt is derived from the configuration (see `account::account_salt`), so anyone
+    /// holding it can deploy the account, and deploying it again returns the existing
+    /// account. An account the factory deployed from an earlier release of the channel is
+    /// returned as well. Like `deploy_idempotent`, fails with `DeployedWithDifferentWasm`
+    /// if the factory deployed other wasm at the address, and with `DeploymentUnverified`
+    /// if a contract the factory did not record exists there. `signers` are the account's
+    /// `Signer` values.
+    pub fn deploy_account(
+        env: &Env,
+        signers: Vec<Val>,
+        plugins: Vec<PluginInstallParams>,
+    ) -> Address {
+        let salt = account::account_salt(env, &signers, &plugins);
+        let contract_id = Self::get_deployed_address(env, salt.clone());
+        let channel = String::from_str(env, ACCOUNT_CHANNEL);
+        if registry::get_deployment(env, &contract_id).is_some_and(|deployment| {
+            release::get_release(env, &deployment.wasm_hash)
+                .is_some_and(|release| release.name == channel)
+        }) {
+            return contract_id;
+        }
+
+        let release = release::latest_active_release(env, &channel)
+            .unwrap_or_else(|| panic_with_error!(env, FactoryError::NoReleasePublished));
+        if already_deployed(env, &contract_id, &release.wasm_hash) {
+            return contract_id;
+        }
+        deploy_contract(
+            env,
+            None,
+            release.wasm_hash,
+            salt,
+            vec![env, signers.into_val(env), plugins.into_val(env)],
+        )
+    }
+
+    /// Returns the address `deploy_account` deploys the given configuration to, whether
+    /// or not it is deployed yet.
+    pub fn predict_account_address(
+        env: &Env,
+        signers: Vec<Val>,
+        plugins: Vec<PluginInstallParams>,
+    ) -> Address {
+        Self::get_deployed_address(env, account::account_salt(env, &signers, &plugins))
+    }
+
+    /// Returns whether `contract_id` was deployed by this factory.
+    pub fn is_deployed_by_factory(env: &Env, contract_id: Address) -> bool {
+        registry::get_deployment(env, &contract_id).is_some()
+    }
+
+    pub fn deployment_info(env: &Env, contract_id: Address) -> Option<Deployment> {
+        registry::get_deployment(env, &contract_id)
+    }
+
+    /// Lists the contracts deployed by the factory in deployment order.
+    pub fn list_deployments(env: &Env, start: u32, limit: u32) -> Vec<Deployment> {
+        registry::list_deployments(env, start, limit)
+    }
+
+    /// Publishes a wasm release as `name@version`, e.g. `smart-account@1.4.0`, for
+    /// deployments and for accounts trusting this factory to upgrade to.
+    ///
+    /// This has to be authorized by an address with the `publisher` role. Wasm hashes and
+    /// versions within a channel can only be published once. Releases start active.
+    #[only_role(caller, "publisher")]
+    pub fn publish_release(
+        env: &Env,
+        caller: Address,
+        wasm_hash: BytesN<32>,
+        name: String,
+        version: String,
+        notes: String,
+    ) {
+        release::store_release(
+            env,
+            &Release {
+                wasm_hash: wasm_hash.clone(),
+                name: name.clone(),
+                version: version.clone(),
+                notes,
+                published_at: env.ledger().timestamp(),
+                status: ReleaseStatus::Active,
+            },
+        );
+
+        env.events().publish(
+            vec![env, RELEASE_PUBLISHED],
+            ReleasePublishedEvent {
+                wasm_hash,
+                name,
+                version,
+            },
+        );
+    }
+
+    /// Deprecates or revokes a release, or reactivates a deprecated one.
+    ///
+    /// Revoking is final. This has to be authorized by an address with the `publisher` role.
+    #[only_role(caller, "publisher")]
+    pub fn set_release_status(
+        env: &Env,
+        caller: Address,
+        wasm_hash: BytesN<32>,
+        status: ReleaseStatus,
+    ) {
+        release::set_release_status(env, &wasm_hash, status);
+
+        env.events().publish(
+            vec![env, RELEASE_STATUS],
+            ReleaseStatusChangedEvent { wasm_hash, status },
+        );
+    }
+
+    pub fn get_release(env: &Env, wasm_hash: BytesN<32>) -> Option<Release> {
+        release::get_release(env, &wasm_hash)
+    }
+
+    pub fn get_release_by_version(env: &Env, name: String, version: String) -> Option<Release> {
+        release::get_release_by_version(env, &name, &version)
+    }
+
+    /// Lists published releases in publication order.
+    pub fn list_releases(env: &Env, start: u32, limit: u32) -> Vec<Release> {
+        release::list_releases(env, start, limit)
+    }
+}
+
+#[contractimpl]
+impl ReleaseRegistry for ContractFactory {
+    /// Revoked releases are no longer published.
+    fn is_published(env: &Env, wasm_hash: BytesN<32>) -> bool {
+        release::get_release(env, &wasm_hash)
+            .is_some_and(|release| release.status != ReleaseStatus::Revoked)
+    }
+}
+
+/// Deploys a contract on behalf of the factory, records it in the registry and publishes
+/// a `ContractDeployedEvent`. Every deployment goes through here, so this is where the
+/// wasm of revoked releases is refused.
+fn deploy_contract(
+    env: &Env,
+    deployer: Option<Address>,
+    wasm_hash: BytesN<32>,
+    salt: BytesN<32>,
+    constructor_args: Vec<Val>,
+) -> Address {
+    if let Err(err) = check_deployable(env, &wasm_hash) {
+        panic_with_error!(env, err);
+    }
+
+    let contract_id = env
+        .deployer()
+        .with_current_contract(salt.clone())
+        .deploy_v2(wasm_hash.clone(), constructor_args);
+
+    registry::record_deployment(
+        env,
+        &Deployment {
+            contract_id: contract_id.clone(),
+            wasm_hash,
+            deployer,
+            deployed_at: env.ledger().timestamp(),
+            salt,
+        },
+    );
+    publish_deployed(env, &contract_id);
+    contract_id
+}
+
+fn publish_deployed(env: &Env, contract_id: &Address) {
+    env.events().publish(
+        vec![env, DEPLOYED_CONTRACT],
+        vec![
+            env,
+            ContractDeployedEvent {
+                contract_id: contract_id.clone(),
+            },
+        ],
+    );
+}
+
+/// Fails with `ReleaseRevoked` if `wasm_hash` is the wasm of a revoked release.
+fn check_deployable(env: &Env, wasm_hash: &BytesN<32>) -> Result<(), FactoryError> {
+    if release::get_release(env, wasm_hash)
+        .is_some_and(|release| release.status == ReleaseStatus::Revoked)
+    {
+        return Err(FactoryError::ReleaseRevoked);
+    }
+    Ok(())
+}
+
+/// Returns whether the factory deployed `wasm_hash` at `contract_id`, or false if there is
+/// no contract there.
+///
+/// Fails with `DeployedWithDifferentWasm` if the factory deployed other wasm there, and
+/// with `DeploymentUnverified` if a contract the factory did not record, e.g. one deployed
+/// before the registry, answers `is_deployed`.
+fn existing_deployment(
+    env: &Env,
+    contract_id: &Address,
+    wasm_hash: &BytesN<32>,
+) -> Result<bool, FactoryError> {
+    match registry::get_deployment(env, contract_id) {
+        Some(deployment) if deployment.wasm_hash == *wasm_hash => Ok(true),
+        Some(_) => Err(FactoryError::DeployedWithDifferentWasm),
+        None if is_deployed(env, contract_id) => Err(FactoryError::DeploymentUnverified),
+        None => Ok(false),
+    }
+}
+
+/// Like `existing_deployment`, but panics with its error.
+fn already_deployed(env: &Env, contract_id: &Address, wasm_hash: &BytesN<32>) -> bool {
+    existing_deployment(env, contract_id, wasm_hash)
+        .unwrap_or_else(|err| panic_with_error!(env, err))
+}
+
+/// Status reporting a batch item skipped because of `err`.
+fn failure_status(env: &Env, err: FactoryError) -> DeploymentStatus {
+    match err {
+        FactoryError::DeployedWithDifferentWasm => DeploymentStatus::DeployedWithDifferentWasm,
+        FactoryError::DeploymentUnverified => DeploymentStatus::DeploymentUnverified,
+        FactoryError::ReleaseRevoked => DeploymentStatus::ReleaseRevoked,
+        err => panic_with_error!(env, err),
+    }
+}
+
+/// Returns whether the factory deployed `contract_id`, or a contract answering
+/// `is_deployed` exists there, e.g. one deployed before the registry.
+fn is_deployed(env: &Env, contract_id: &Address) -> bool {
+    registry::get_deployment(env, contract_id).is_some()
+        || env
+            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                contract_id,
+                &Symbol::new(env, "is_deployed"),
+                Vec::new(env),
+            )
+            .is_ok()
 }
 
 #[default_impl]
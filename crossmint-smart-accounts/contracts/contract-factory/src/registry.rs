@@ -0,0 +1,58 @@
+use soroban_sdk::{
+    contracttype, panic_with_error, symbol_short, Address, BytesN, Env, Symbol, Vec,
+};
+use storage::{Namespace, StorageMap};
+
+use crate::release::{or_panic, FactoryError};
+
+const DEPLOYMENTS_KEY: Symbol = symbol_short!("deploys");
+
+/// Maximum number of deployments returned by a single `list_deployments` call.
+pub const MAX_DEPLOYMENTS_PAGE: u32 = 50;
+
+/// A contract deployed by the factory.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deployment {
+    pub contract_id: Address,
+    /// Wasm the contract was deployed with. Later upgrades of the contract are not tracked.
+    pub wasm_hash: BytesN<32>,
+    /// Holder of the `deployer` role who deployed the contract. `None` for accounts
+    /// deployed through `deploy_account`, which anyone may call.
+    pub deployer: Option<Address>,
+    pub deployed_at: u64,
+    pub salt: BytesN<32>,
+}
+
+/// Deployments by contract id, in deployment order. Entries are never removed, so the
+/// order of the map is preserved.
+fn deployments() -> StorageMap<Address, Deployment> {
+    StorageMap::new(Namespace::Factory, DEPLOYMENTS_KEY)
+}
+
+pub fn get_deployment(env: &Env, contract_id: &Address) -> Option<Deployment> {
+    deployments().get(env, contract_id)
+}
+
+pub fn deployment_count(env: &Env) -> u32 {
+    deployments().len(env)
+}
+
+pub fn record_deployment(env: &Env, deployment: &Deployment) {
+    or_panic(
+        env,
+        deployments().set(env, &deployment.contract_id, deployment),
+    );
+}
+
+/// Returns up to `limit` deployments in deployment order, starting at position `start`.
+pub fn list_deployments(env: &Env, start: u32, limit: u32) -> Vec<Deployment> {
+    if limit > MAX_DEPLOYMENTS_PAGE {
+        panic_with_error!(env, FactoryError::InvalidPage);
+    }
+    let mut list = Vec::new(env);
+    for (_, deployment) in deployments().entries(env, start, limit).iter() {
+        list.push_back(deployment);
+    }
+    list
+}
@@ -0,0 +1,203 @@
+use soroban_sdk::{
+    contracterror, contracttype, panic_with_error, symbol_short, BytesN, Env, String, Symbol, Vec,
+};
+use storage::{Error as StorageError, Namespace, StorageMap};
+
+const RELEASES_KEY: Symbol = symbol_short!("releases");
+const VERSIONS_KEY: Symbol = symbol_short!("versions");
+const LATEST_KEY: Symbol = symbol_short!("latest");
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FactoryError {
+    /// A release with this wasm hash was already published
+    ReleaseAlreadyPublished = 1,
+    /// A release with this name and version was already published
+    VersionAlreadyPublished = 2,
+    /// The requested page is larger than `MAX_RELEASES_PAGE` or `MAX_DEPLOYMENTS_PAGE`
+    InvalidPage = 3,
+    /// An account was deployed while no release is active
+    NoReleasePublished = 4,
+    /// A contract of an atomic batch already exists
+    AlreadyDeployed = 5,
+    /// A batch holds more than `MAX_BATCH_SIZE` deployments
+    BatchTooLarge = 6,
+    /// No release was published with this wasm hash, or name and version
+    ReleaseNotFound = 7,
+    /// The release was revoked and can no longer be deployed nor have its status changed
+    ReleaseRevoked = 8,
+    /// `upload_and_deploy` was called by a deployer without the `uploader` role
+    UploaderRoleRequired = 9,
+    /// Storage entry was not found
+    StorageEntryNotFound = 10,
+    /// Storage entry already exists
+    StorageEntryAlreadyExists = 11,
+    /// An idempotent deployment found a contract the factory deployed with another wasm
+    DeployedWithDifferentWasm = 12,
+    /// An idempotent deployment found a contract the factory did not deploy, whose wasm
+    /// cannot be checked
+    DeploymentUnverified = 13,
+}
+
+impl From<StorageError> for FactoryError {
+    fn from(e: StorageError) -> Self {
+        match e {
+            StorageError::NotFound => FactoryError::StorageEntryNotFound,
+            StorageError::AlreadyExists => FactoryError::StorageEntryAlreadyExists,
+        }
+    }
+}
+
+/// Maximum number of releases returned by a single `list_releases` call.
+pub const MAX_RELEASES_PAGE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReleaseStatus {
+    Active,
+    /// Still deployable and upgradable to, but superseded
+    Deprecated,
+    /// Known to be unsafe: not deployable, and no longer published to accounts
+    Revoked,
+}
+
+/// A wasm release published for accounts to deploy and upgrade to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Release {
+    pub wasm_hash: BytesN<32>,
+    /// Release channel, e.g. `smart-account`
+    pub name: String,
+    /// Semantic version of the release, unique within its channel
+    pub version: String,
+    pub notes: String,
+    pub published_at: u64,
+    pub status: ReleaseStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleasePublishedEvent {
+    pub wasm_hash: BytesN<32>,
+    pub name: String,
+    pub version: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseStatusChangedEvent {
+    pub wasm_hash: BytesN<32>,
+    pub status: ReleaseStatus,
+}
+
+/// Releases by wasm hash, in publication order. Entries are never removed, so the order of
+/// the map is preserved.
+fn releases() -> StorageMap<BytesN<32>, Release> {
+    StorageMap::new(Namespace::Factory, RELEASES_KEY)
+}
+
+/// Wasm hash of the release published under each name and version.
+fn versions() -> StorageMap<(String, String), BytesN<32>> {
+    StorageMap::new(Namespace::Factory, VERSIONS_KEY)
+}
+
+/// Wasm hash of the most recently published active release of each channel.
+fn latest_active() -> StorageMap<String, BytesN<32>> {
+    StorageMap::new(Namespace::Factory, LATEST_KEY)
+}
+
+pub(crate) fn or_panic<T>(env: &Env, result: Result<T, StorageError>) -> T {
+    result.unwrap_or_else(|err| panic_with_error!(env, FactoryError::from(err)))
+}
+
+pub fn get_release(env: &Env, wasm_hash: &BytesN<32>) -> Option<Release> {
+    releases().get(env, wasm_hash)
+}
+
+pub fn release_count(env: &Env) -> u32 {
+    releases().len(env)
+}
+
+pub fn get_release_by_version(env: &Env, name: &String, version: &String) -> Option<Release> {
+    let wasm_hash = versions().get(env, &(name.clone(), version.clone()))?;
+    get_release(env, &wasm_hash)
+}
+
+/// Returns the most recently published release of the `name` channel that is still active.
+///
+/// Reads the channel's pointer, kept up to date by `store_release` and
+/// `set_release_status`, so deploying an account does not depend on the number of releases.
+pub fn latest_active_release(env: &Env, name: &String) -> Option<Release> {
+    let wasm_hash = latest_active().get(env, name)?;
+    get_release(env, &wasm_hash)
+}
+
+/// Points the `name` channel at its most recently published active release, or at none.
+///
+/// Scans the releases from the most recent one. Only status changes pay for it, since a new
+/// release is always the latest active one of its channel.
+fn refresh_latest_active(env: &Env, name: &String) {
+    let releases = releases();
+    let mut end = releases.len(env);
+    while end > 0 {
+        let start = end.saturating_sub(MAX_RELEASES_PAGE);
+        let latest = releases
+            .entries(env, start, end - start)
+            .iter()
+            .rev()
+            .map(|(_, release)| release)
+            .find(|release| release.name == *name && release.status == ReleaseStatus::Active);
+        if let Some(latest) = latest {
+            or_panic(env, latest_active().set(env, name, &latest.wasm_hash));
+            return;
+        }
+        end = start;
+    }
+    or_panic(env, latest_active().remove(env, name));
+}
+
+pub fn store_release(env: &Env, release: &Release) {
+    let releases = releases();
+    if releases.contains(env, &release.wasm_hash) {
+        panic_with_error!(env, FactoryError::ReleaseAlreadyPublished);
+    }
+    let versions = versions();
+    let version = (release.name.clone(), release.version.clone());
+    if versions.contains(env, &version) {
+        panic_with_error!(env, FactoryError::VersionAlreadyPublished);
+    }
+
+    or_panic(env, releases.set(env, &release.wasm_hash, release));
+    or_panic(env, versions.set(env, &version, &release.wasm_hash));
+    if release.status == ReleaseStatus::Active {
+        or_panic(
+            env,
+            latest_active().set(env, &release.name, &release.wasm_hash),
+        );
+    }
+}
+
+/// Changes the status of a release. Revoked releases are final.
+pub fn set_release_status(env: &Env, wasm_hash: &BytesN<32>, status: ReleaseStatus) {
+    let mut release = get_release(env, wasm_hash)
+        .unwrap_or_else(|| panic_with_error!(env, FactoryError::ReleaseNotFound));
+    if release.status == ReleaseStatus::Revoked {
+        panic_with_error!(env, FactoryError::ReleaseRevoked);
+    }
+    release.status = status;
+    or_panic(env, releases().set(env, wasm_hash, &release));
+    refresh_latest_active(env, &release.name);
+}
+
+/// Returns up to `limit` releases in publication order, starting at position `start`.
+pub fn list_releases(env: &Env, start: u32, limit: u32) -> Vec<Release> {
+    if limit > MAX_RELEASES_PAGE {
+        panic_with_error!(env, FactoryError::InvalidPage);
+    }
+    let mut list = Vec::new(env);
+    for (_, release) in releases().entries(env, start, limit).iter() {
+        list.push_back(release);
+    }
+    list
+}
@@ -2,12 +2,22 @@
 
 extern crate std;
 
+use smart_account_interfaces::PluginInstallParams;
 use soroban_sdk::{
-    symbol_short, testutils::Address as _, vec, Address, BytesN, Env, IntoVal, Val, Vec,
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, BytesN as _, Events as _, Ledger as _},
+    vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, String, Val, Vec,
 };
 
-use crate::test_constants::SMART_ACCOUNT_WASM;
-use crate::{ContractDeploymentArgs, ContractFactory, ContractFactoryClient};
+use crate::registry::{Deployment, MAX_DEPLOYMENTS_PAGE};
+use crate::release::{self, FactoryError, ReleaseStatus};
+use crate::test_constants::{ACCOUNT_CONSTRUCTOR_WASM, SMART_ACCOUNT_WASM};
+use crate::{
+    account, BatchDeployedEvent, ContractDeploymentArgs, ContractFactory, ContractFactoryClient,
+    DeploymentResult, DeploymentStatus, MAX_BATCH_SIZE,
+};
 
 fn create_factory_client<'a>(e: &Env, admin: &Address) -> ContractFactoryClient<'a> {
     let address = e.register(ContractFactory, (admin,));
@@ -446,6 +456,7 @@ fn test_upload_and_deploy_function_exists() {
     let client = create_factory_client(&e, &admin);
 
     let accounts = setup_roles(&e, &client, &admin);
+    client.grant_role(&admin, &accounts.deployer1, &symbol_short!("uploader"));
     let salt = create_mock_salt(&e, 1);
 
     let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
@@ -454,6 +465,980 @@ fn test_upload_and_deploy_function_exists() {
     let deployed_address =
         client.upload_and_deploy(&accounts.deployer1, &wasm_bytes, &salt, &constructor_args);
 
-    // Verify that deployment actually worked by checking the address is valid
-    assert!(!deployed_address.to_string().is_empty());
+    // Same address space, event and registry entry as `deploy`
+    assert_eq!(deployed_address, client.get_deployed_address(&salt));
+    let (_, topics, _) = e.events().all().last().unwrap();
+    assert_eq!(topics, vec![&e, symbol_short!("DEPLOYED").into_val(&e)]);
+    let deployment = client.deployment_info(&deployed_address).unwrap();
+    assert_eq!(deployment.deployer, Some(accounts.deployer1.clone()));
+    assert_eq!(
+        deployment.wasm_hash,
+        e.deployer().upload_contract_wasm(wasm_bytes)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_upload_and_deploy_requires_uploader_role() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    client.upload_and_deploy(
+        &accounts.deployer1,
+        &wasm_bytes,
+        &create_mock_salt(&e, 1),
+        &vec![&e],
+    );
+}
+
+#[test]
+fn test_uploader_uploads_wasm_for_deployers() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+    let uploader = Address::generate(&e);
+    client.grant_role(&admin, &uploader, &symbol_short!("uploader"));
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let wasm_hash = client.upload_wasm(&uploader, &wasm_bytes);
+    assert_eq!(wasm_hash, e.deployer().upload_contract_wasm(wasm_bytes));
+
+    let deployment_args = ContractDeploymentArgs {
+        wasm_hash,
+        salt: create_mock_salt(&e, 1),
+        constructor_args: vec![&e],
+    };
+    let first = client.deploy_idempotent(&accounts.deployer1, &deployment_args);
+    assert_eq!(first.status, DeploymentStatus::NewlyDeployed);
+    let second = client.deploy_idempotent(&accounts.deployer1, &deployment_args);
+    assert_eq!(second.contract_id, first.contract_id);
+    assert_eq!(second.status, DeploymentStatus::AlreadyDeployedSame);
+}
+
+#[test]
+#[should_panic]
+fn test_deployer_cannot_upload_wasm() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    client.upload_wasm(&accounts.deployer1, &wasm_bytes);
+}
+
+fn account_channel(e: &Env) -> String {
+    String::from_str(e, "smart-account")
+}
+
+fn grant_publisher(e: &Env, client: &ContractFactoryClient, admin: &Address) -> Address {
+    let publisher = Address::generate(e);
+    client.grant_role(admin, &publisher, &symbol_short!("publisher"));
+    publisher
+}
+
+#[test]
+fn test_publish_release() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+
+    let wasm_hash = BytesN::random(&e);
+    assert!(!client.is_published(&wasm_hash));
+
+    let version = String::from_str(&e, "1.2.0");
+    let notes = String::from_str(&e, "Upgrade timelock");
+    client.publish_release(
+        &publisher,
+        &wasm_hash,
+        &account_channel(&e),
+        &version,
+        &notes,
+    );
+
+    assert!(client.is_published(&wasm_hash));
+    let release = client.get_release(&wasm_hash).unwrap();
+    assert_eq!(release.name, account_channel(&e));
+    assert_eq!(release.version, version);
+    assert_eq!(release.notes, notes);
+    assert_eq!(release.status, ReleaseStatus::Active);
+    assert_eq!(
+        client.get_release_by_version(&account_channel(&e), &version),
+        Some(release)
+    );
+    assert_eq!(client.get_release(&BytesN::random(&e)), None);
+}
+
+#[test]
+fn test_list_releases_in_publication_order() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+
+    let versions = ["1.0.0", "1.1.0", "2.0.0"];
+    for version in versions {
+        client.publish_release(
+            &publisher,
+            &BytesN::random(&e),
+            &account_channel(&e),
+            &String::from_str(&e, version),
+            &String::from_str(&e, ""),
+        );
+    }
+
+    let first_page = client.list_releases(&0, &2);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(
+        first_page.get(0).unwrap().version,
+        String::from_str(&e, "1.0.0")
+    );
+    assert_eq!(
+        first_page.get(1).unwrap().version,
+        String::from_str(&e, "1.1.0")
+    );
+
+    let last_page = client.list_releases(&2, &2);
+    assert_eq!(last_page.len(), 1);
+    assert_eq!(
+        last_page.get(0).unwrap().version,
+        String::from_str(&e, "2.0.0")
+    );
+    assert_eq!(client.list_releases(&3, &2).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_publish_release_twice_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+
+    let wasm_hash = BytesN::random(&e);
+    let notes = String::from_str(&e, "");
+    client.publish_release(
+        &publisher,
+        &wasm_hash,
+        &account_channel(&e),
+        &String::from_str(&e, "1.0.0"),
+        &notes,
+    );
+    client.publish_release(
+        &publisher,
+        &wasm_hash,
+        &account_channel(&e),
+        &String::from_str(&e, "1.0.1"),
+        &notes,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_publish_release_version_reuse_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+
+    let version = String::from_str(&e, "1.0.0");
+    let notes = String::from_str(&e, "");
+    let name = account_channel(&e);
+    client.publish_release(&publisher, &BytesN::random(&e), &name, &version, &notes);
+    client.publish_release(&publisher, &BytesN::random(&e), &name, &version, &notes);
+}
+
+#[test]
+#[should_panic]
+fn test_non_publisher_cannot_publish_release() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    client.publish_release(
+        &accounts.deployer1,
+        &BytesN::random(&e),
+        &account_channel(&e),
+        &String::from_str(&e, "1.0.0"),
+        &String::from_str(&e, ""),
+    );
+}
+
+fn account_config(e: &Env, signer: &BytesN<32>) -> (Vec<Val>, Vec<PluginInstallParams>) {
+    let plugins = vec![
+        e,
+        PluginInstallParams {
+            plugin: Address::generate(e),
+            config: Bytes::new(e),
+        },
+    ];
+    (vec![e, signer.into_val(e)], plugins)
+}
+
+#[test]
+fn test_predict_account_address_is_derived_from_config() {
+    let e = Env::default();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+
+    let (signers, plugins) = account_config(&e, &BytesN::random(&e));
+    let predicted = client.predict_account_address(&signers, &plugins);
+    assert_eq!(
+        predicted,
+        client.predict_account_address(&signers, &plugins)
+    );
+
+    let salt = e.as_contract(&client.address, || {
+        account::account_salt(&e, &signers, &plugins)
+    });
+    assert_eq!(predicted, client.get_deployed_address(&salt));
+
+    // Any change to the configuration moves the account
+    let (other_signers, _) = account_config(&e, &BytesN::random(&e));
+    assert_ne!(
+        predicted,
+        client.predict_account_address(&other_signers, &plugins)
+    );
+    assert_ne!(
+        predicted,
+        client.predict_account_address(&signers, &Vec::new(&e))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_deploy_account_requires_published_release() {
+    let e = Env::default();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+
+    let (signers, plugins) = account_config(&e, &BytesN::random(&e));
+    client.deploy_account(&signers, &plugins);
+}
+
+fn batch_item(e: &Env, wasm_hash: &BytesN<32>, salt: u8) -> ContractDeploymentArgs {
+    ContractDeploymentArgs {
+        wasm_hash: wasm_hash.clone(),
+        salt: create_mock_salt(e, salt),
+        constructor_args: vec![e],
+    }
+}
+
+#[test]
+fn test_deploy_batch_reports_each_item() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    let deployments = vec![
+        &e,
+        batch_item(&e, &wasm_hash, 1),
+        batch_item(&e, &wasm_hash, 2),
+        // Same salt as the first item
+        batch_item(&e, &wasm_hash, 1),
+    ];
+
+    let results = client.deploy_batch(&accounts.deployer1, &deployments, &false);
+    assert_eq!(
+        results,
+        vec![
+            &e,
+            DeploymentResult {
+                contract_id: client.get_deployed_address(&create_mock_salt(&e, 1)),
+                status: DeploymentStatus::NewlyDeployed,
+            },
+            DeploymentResult {
+                contract_id: client.get_deployed_address(&create_mock_salt(&e, 2)),
+                status: DeploymentStatus::NewlyDeployed,
+            },
+            DeploymentResult {
+                contract_id: client.get_deployed_address(&create_mock_salt(&e, 1)),
+                status: DeploymentStatus::AlreadyDeployedSame,
+            },
+        ]
+    );
+
+    // One event per new contract, plus the aggregate event
+    let events = e.events().all();
+    let deployed_topics: Vec<Val> = vec![&e, symbol_short!("DEPLOYED").into_val(&e)];
+    let deployed_events = events
+        .iter()
+        .filter(|(_, topics, _)| *topics == deployed_topics)
+        .count();
+    assert_eq!(deployed_events, 2);
+    let (_, topics, data) = events.last().unwrap();
+    assert_eq!(topics, vec![&e, symbol_short!("BATCH").into_val(&e)]);
+    let summary: BatchDeployedEvent = data.into_val(&e);
+    assert_eq!(
+        summary,
+        BatchDeployedEvent {
+            deployed: 2,
+            already_deployed: 1,
+            failed: 0,
+        }
+    );
+}
+
+#[test]
+fn test_deploy_batch_reports_failed_items() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    let revoked_hash = BytesN::random(&e);
+    client.publish_release(
+        &publisher,
+        &revoked_hash,
+        &account_channel(&e),
+        &String::from_str(&e, "1.0.0"),
+        &String::from_str(&e, ""),
+    );
+    client.set_release_status(&publisher, &revoked_hash, &ReleaseStatus::Revoked);
+    client.deploy(&accounts.deployer1, &batch_item(&e, &wasm_hash, 1));
+    e.register_at(
+        &client.get_deployed_address(&create_mock_salt(&e, 2)),
+        UnrecordedAccount,
+        (),
+    );
+
+    let deployments = vec![
+        &e,
+        batch_item(&e, &wasm_hash, 1),
+        // Other wasm where the factory deployed `wasm_hash`
+        batch_item(&e, &BytesN::random(&e), 1),
+        // A contract the factory did not deploy
+        batch_item(&e, &wasm_hash, 2),
+        batch_item(&e, &revoked_hash, 3),
+        batch_item(&e, &wasm_hash, 4),
+    ];
+    let statuses: std::vec::Vec<DeploymentStatus> = client
+        .deploy_batch(&accounts.deployer1, &deployments, &false)
+        .iter()
+        .map(|result| result.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            DeploymentStatus::AlreadyDeployedSame,
+            DeploymentStatus::DeployedWithDifferentWasm,
+            DeploymentStatus::DeploymentUnverified,
+            DeploymentStatus::ReleaseRevoked,
+            DeploymentStatus::NewlyDeployed,
+        ]
+    );
+
+    // Failed items are skipped, the others are deployed
+    assert!(!client.is_deployed_by_factory(&client.get_deployed_address(&create_mock_salt(&e, 3))));
+    assert!(client.is_deployed_by_factory(&client.get_deployed_address(&create_mock_salt(&e, 4))));
+    let (_, _, data) = e.events().all().last().unwrap();
+    let summary: BatchDeployedEvent = data.into_val(&e);
+    assert_eq!(
+        summary,
+        BatchDeployedEvent {
+            deployed: 1,
+            already_deployed: 1,
+            failed: 3,
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_atomic_deploy_batch_fails_on_existing_contract() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    let deployments = vec![
+        &e,
+        batch_item(&e, &wasm_hash, 1),
+        batch_item(&e, &wasm_hash, 1),
+    ];
+
+    client.deploy_batch(&accounts.deployer1, &deployments, &true);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_deploy_batch_size_is_bounded() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_hash = BytesN::random(&e);
+    let mut deployments = Vec::new(&e);
+    for salt in 0..=MAX_BATCH_SIZE {
+        deployments.push_back(batch_item(&e, &wasm_hash, salt as u8));
+    }
+
+    client.deploy_batch(&accounts.deployer1, &deployments, &false);
+}
+
+#[test]
+#[should_panic]
+fn test_non_deployer_cannot_deploy_batch() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    client.deploy_batch(
+        &accounts.outsider,
+        &vec![&e, batch_item(&e, &wasm_hash, 1)],
+        &false,
+    );
+}
+
+#[test]
+fn test_registry_records_deployments() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_timestamp(1_700);
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    let first = client.deploy(&accounts.deployer1, &batch_item(&e, &wasm_hash, 1));
+    let second = client
+        .deploy_idempotent(&accounts.deployer2, &batch_item(&e, &wasm_hash, 2))
+        .contract_id;
+
+    assert!(client.is_deployed_by_factory(&first));
+    assert!(!client.is_deployed_by_factory(&Address::generate(&e)));
+    assert_eq!(
+        client.deployment_info(&first),
+        Some(Deployment {
+            contract_id: first.clone(),
+            wasm_hash: wasm_hash.clone(),
+            deployer: Some(accounts.deployer1.clone()),
+            deployed_at: 1_700,
+            salt: create_mock_salt(&e, 1),
+        })
+    );
+    assert_eq!(client.deployment_info(&Address::generate(&e)), None);
+
+    let deployments = client.list_deployments(&0, &MAX_DEPLOYMENTS_PAGE);
+    assert_eq!(deployments.len(), 2);
+    assert_eq!(deployments.get(0).unwrap().contract_id, first);
+    assert_eq!(deployments.get(1).unwrap().contract_id, second);
+    assert_eq!(
+        deployments.get(1).unwrap().deployer,
+        Some(accounts.deployer2.clone())
+    );
+    assert_eq!(client.list_deployments(&1, &5).len(), 1);
+    assert_eq!(client.list_deployments(&2, &5).len(), 0);
+}
+
+#[test]
+fn test_deploy_idempotent_uses_registry() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    let deployed = client.deploy(&accounts.deployer1, &batch_item(&e, &wasm_hash, 1));
+
+    // The deployed wasm does not answer `is_deployed`, the registry still knows it
+    let again = client.deploy_idempotent(&accounts.deployer1, &batch_item(&e, &wasm_hash, 1));
+    assert_eq!(
+        again,
+        DeploymentResult {
+            contract_id: deployed,
+            status: DeploymentStatus::AlreadyDeployedSame,
+        }
+    );
+    assert_eq!(client.list_deployments(&0, &5).len(), 1);
+}
+
+#[test]
+fn test_deploy_idempotent_fails_on_different_code() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    let deployed = client.deploy(&accounts.deployer1, &batch_item(&e, &wasm_hash, 1));
+
+    // Requesting other code at the same address neither deploys nor reports success
+    let different: soroban_sdk::Error = FactoryError::DeployedWithDifferentWasm.into();
+    let item = batch_item(&e, &BytesN::random(&e), 1);
+    assert_eq!(
+        client
+            .try_deploy_idempotent(&accounts.deployer1, &item)
+            .unwrap_err()
+            .unwrap(),
+        different
+    );
+    assert_eq!(
+        client
+            .try_deploy_batch(&accounts.deployer1, &vec![&e, item.clone()], &true)
+            .unwrap_err()
+            .unwrap(),
+        different
+    );
+    assert_eq!(
+        client
+            .deploy_batch(&accounts.deployer1, &vec![&e, item], &false)
+            .get(0)
+            .unwrap()
+            .status,
+        DeploymentStatus::DeployedWithDifferentWasm
+    );
+    assert_eq!(
+        client.deployment_info(&deployed).unwrap().wasm_hash,
+        wasm_hash
+    );
+}
+
+/// Stands for an account deployed before the registry, which answers `is_deployed`.
+#[contract]
+struct UnrecordedAccount;
+
+#[contractimpl]
+impl UnrecordedAccount {
+    pub fn is_deployed() -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_deploy_idempotent_fails_on_unrecorded_contract() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    let item = batch_item(&e, &wasm_hash, 1);
+    e.register_at(
+        &client.get_deployed_address(&item.salt),
+        UnrecordedAccount,
+        (),
+    );
+
+    let unverified: soroban_sdk::Error = FactoryError::DeploymentUnverified.into();
+    assert_eq!(
+        client
+            .try_deploy_idempotent(&accounts.deployer1, &item)
+            .unwrap_err()
+            .unwrap(),
+        unverified
+    );
+    assert_eq!(
+        client
+            .try_deploy_batch(&accounts.deployer1, &vec![&e, item.clone()], &true)
+            .unwrap_err()
+            .unwrap(),
+        unverified
+    );
+    assert_eq!(
+        client
+            .deploy_batch(&accounts.deployer1, &vec![&e, item], &false)
+            .get(0)
+            .unwrap()
+            .status,
+        DeploymentStatus::DeploymentUnverified
+    );
+    assert_eq!(client.list_deployments(&0, &5).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_list_deployments_page_is_bounded() {
+    let e = Env::default();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+
+    client.list_deployments(&0, &(MAX_DEPLOYMENTS_PAGE + 1));
+}
+
+/// Publishes the test wasm as `smart-account@<version>` and returns its hash.
+fn publish_account_release(
+    e: &Env,
+    client: &ContractFactoryClient,
+    publisher: &Address,
+    version: &str,
+) -> BytesN<32> {
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(e, SMART_ACCOUNT_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    client.publish_release(
+        publisher,
+        &wasm_hash,
+        &account_channel(e),
+        &String::from_str(e, version),
+        &String::from_str(e, ""),
+    );
+    wasm_hash
+}
+
+#[test]
+fn test_versions_are_scoped_to_their_channel() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+
+    let version = String::from_str(&e, "1.0.0");
+    let notes = String::from_str(&e, "");
+    let account_hash = BytesN::random(&e);
+    let plugin_hash = BytesN::random(&e);
+    let plugin_channel = String::from_str(&e, "spending-limit");
+    client.publish_release(
+        &publisher,
+        &account_hash,
+        &account_channel(&e),
+        &version,
+        &notes,
+    );
+    client.publish_release(&publisher, &plugin_hash, &plugin_channel, &version, &notes);
+
+    assert_eq!(
+        client
+            .get_release_by_version(&plugin_channel, &version)
+            .unwrap()
+            .wasm_hash,
+        plugin_hash
+    );
+    assert_eq!(
+        client.get_release_by_version(&plugin_channel, &String::from_str(&e, "2.0.0")),
+        None
+    );
+}
+
+#[test]
+fn test_deploy_release() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+    let wasm_hash = publish_account_release(&e, &client, &publisher, "1.4.0");
+
+    // Deprecated releases remain deployable
+    client.set_release_status(&publisher, &wasm_hash, &ReleaseStatus::Deprecated);
+    let salt = create_mock_salt(&e, 1);
+    let contract_id = client.deploy_release(
+        &accounts.deployer1,
+        &account_channel(&e),
+        &String::from_str(&e, "1.4.0"),
+        &salt,
+        &vec![&e],
+    );
+
+    assert_eq!(contract_id, client.get_deployed_address(&salt));
+    assert_eq!(
+        client.deployment_info(&contract_id).unwrap().wasm_hash,
+        wasm_hash
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_revoked_release_cannot_be_deployed() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+    let wasm_hash = publish_account_release(&e, &client, &publisher, "1.4.0");
+
+    client.set_release_status(&publisher, &wasm_hash, &ReleaseStatus::Revoked);
+    assert!(!client.is_published(&wasm_hash));
+
+    client.deploy_release(
+        &accounts.deployer1,
+        &account_channel(&e),
+        &String::from_str(&e, "1.4.0"),
+        &create_mock_salt(&e, 1),
+        &vec![&e],
+    );
+}
+
+#[test]
+fn test_revoked_release_cannot_be_deployed_by_hash() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+    let wasm_hash = publish_account_release(&e, &client, &publisher, "1.4.0");
+    client.set_release_status(&publisher, &wasm_hash, &ReleaseStatus::Revoked);
+
+    let revoked: soroban_sdk::Error = FactoryError::ReleaseRevoked.into();
+    let item = batch_item(&e, &wasm_hash, 1);
+    assert_eq!(
+        client
+            .try_deploy(&accounts.deployer1, &item)
+            .unwrap_err()
+            .unwrap(),
+        revoked
+    );
+    assert_eq!(
+        client
+            .try_deploy_idempotent(&accounts.deployer1, &item)
+            .unwrap_err()
+            .unwrap(),
+        revoked
+    );
+    assert_eq!(
+        client
+            .try_deploy_batch(&accounts.deployer1, &vec![&e, item.clone()], &true)
+            .unwrap_err()
+            .unwrap(),
+        revoked
+    );
+    assert_eq!(
+        client
+            .deploy_batch(&accounts.deployer1, &vec![&e, item], &false)
+            .get(0)
+            .unwrap()
+            .status,
+        DeploymentStatus::ReleaseRevoked
+    );
+    assert_eq!(client.list_deployments(&0, &MAX_DEPLOYMENTS_PAGE).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_deploy_unknown_release_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+
+    client.deploy_release(
+        &accounts.deployer1,
+        &account_channel(&e),
+        &String::from_str(&e, "9.9.9"),
+        &create_mock_salt(&e, 1),
+        &vec![&e],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_revoked_release_cannot_be_reactivated() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+    let wasm_hash = publish_account_release(&e, &client, &publisher, "1.4.0");
+
+    client.set_release_status(&publisher, &wasm_hash, &ReleaseStatus::Revoked);
+    client.set_release_status(&publisher, &wasm_hash, &ReleaseStatus::Active);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_deploy_account_skips_inactive_releases() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+    let wasm_hash = publish_account_release(&e, &client, &publisher, "1.4.0");
+    client.set_release_status(&publisher, &wasm_hash, &ReleaseStatus::Deprecated);
+
+    let (signers, plugins) = account_config(&e, &BytesN::random(&e));
+    client.deploy_account(&signers, &plugins);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_deploy_account_ignores_other_channels() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+    client.publish_release(
+        &publisher,
+        &BytesN::random(&e),
+        &String::from_str(&e, "spending-limit"),
+        &String::from_str(&e, "1.0.0"),
+        &String::from_str(&e, ""),
+    );
+
+    let (signers, plugins) = account_config(&e, &BytesN::random(&e));
+    client.deploy_account(&signers, &plugins);
+}
+
+/// Publishes the test wasm with a constructor as `smart-account@<version>`, so that
+/// `deploy_account` can deploy it, and returns its hash.
+fn publish_deployable_account_release(
+    e: &Env,
+    client: &ContractFactoryClient,
+    publisher: &Address,
+    version: &str,
+) -> BytesN<32> {
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(e, ACCOUNT_CONSTRUCTOR_WASM);
+    let wasm_hash = e.deployer().upload_contract_wasm(wasm_bytes);
+    client.publish_release(
+        publisher,
+        &wasm_hash,
+        &account_channel(e),
+        &String::from_str(e, version),
+        &String::from_str(e, ""),
+    );
+    wasm_hash
+}
+
+#[test]
+fn test_deploy_account() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+    let wasm_hash = publish_deployable_account_release(&e, &client, &publisher, "1.0.0");
+
+    let (signers, plugins) = account_config(&e, &BytesN::random(&e));
+    let predicted = client.predict_account_address(&signers, &plugins);
+    let account = client.deploy_account(&signers, &plugins);
+    assert_eq!(account, predicted);
+    let deployment = client.deployment_info(&account).unwrap();
+    assert_eq!(deployment.wasm_hash, wasm_hash);
+    assert_eq!(deployment.deployer, None);
+
+    // Deploying the same configuration again returns the account without redeploying it
+    assert_eq!(client.deploy_account(&signers, &plugins), account);
+    assert_eq!(client.list_deployments(&0, &MAX_DEPLOYMENTS_PAGE).len(), 1);
+}
+
+#[test]
+fn test_account_salt_is_domain_separated() {
+    let e = Env::default();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+
+    // A deployer cannot pick the plain hash of a configuration as a salt to take its address
+    let (signers, plugins) = account_config(&e, &BytesN::random(&e));
+    let untagged: BytesN<32> = e
+        .crypto()
+        .sha256(&(signers.clone(), plugins.clone()).to_xdr(&e))
+        .into();
+    assert_ne!(
+        client.predict_account_address(&signers, &plugins),
+        client.get_deployed_address(&untagged)
+    );
+}
+
+#[test]
+fn test_latest_active_release_follows_status_changes() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+    let channel = account_channel(&e);
+    let latest = || {
+        e.as_contract(&client.address, || {
+            release::latest_active_release(&e, &channel).map(|release| release.wasm_hash)
+        })
+    };
+
+    let first = BytesN::random(&e);
+    let second = BytesN::random(&e);
+    let notes = String::from_str(&e, "");
+    client.publish_release(
+        &publisher,
+        &first,
+        &channel,
+        &String::from_str(&e, "1.0.0"),
+        &notes,
+    );
+    assert_eq!(latest(), Some(first.clone()));
+    client.publish_release(
+        &publisher,
+        &second,
+        &channel,
+        &String::from_str(&e, "2.0.0"),
+        &notes,
+    );
+    assert_eq!(latest(), Some(second.clone()));
+
+    // Deprecating the latest release falls back to the previous active one
+    client.set_release_status(&publisher, &second, &ReleaseStatus::Deprecated);
+    assert_eq!(latest(), Some(first.clone()));
+    client.set_release_status(&publisher, &first, &ReleaseStatus::Revoked);
+    assert_eq!(latest(), None);
+
+    // A reactivated release is the latest active one again
+    client.set_release_status(&publisher, &second, &ReleaseStatus::Active);
+    assert_eq!(latest(), Some(second));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_deploy_account_fails_on_squatted_address() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let client = create_factory_client(&e, &admin);
+    let accounts = setup_roles(&e, &client, &admin);
+    let publisher = grant_publisher(&e, &client, &admin);
+    publish_deployable_account_release(&e, &client, &publisher, "1.0.0");
+
+    // A deployer takes the account's address with wasm outside the account channel
+    let (signers, plugins) = account_config(&e, &BytesN::random(&e));
+    let salt = e.as_contract(&client.address, || {
+        account::account_salt(&e, &signers, &plugins)
+    });
+    let wasm_bytes = soroban_sdk::Bytes::from_slice(&e, SMART_ACCOUNT_WASM);
+    let squatter = ContractDeploymentArgs {
+        wasm_hash: e.deployer().upload_contract_wasm(wasm_bytes),
+        salt,
+        constructor_args: vec![&e],
+    };
+    client.deploy(&accounts.deployer1, &squatter);
+
+    client.deploy_account(&signers, &plugins);
 }
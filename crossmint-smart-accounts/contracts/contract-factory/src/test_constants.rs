@@ -77,3 +77,87 @@ pub const SMART_ACCOUNT_WASM: &[u8] = &[
     0x30, 0x32, 0x31, 0x33, 0x62, 0x62, 0x62, 0x37, 0x32, 0x32, 0x38, 0x35, 0x35, 0x36, 0x36, 0x66,
     0x39, 0x64, 0x64, 0x39, 0x36, 0x30, 0x66, 0x66, 0x39, 0x36, 0x64, 0x30, 0x33, 0x64, 0x38, 0x00,
 ];
+
+/// `SMART_ACCOUNT_WASM` with a `__constructor(signers, plugins)` that ignores its arguments,
+/// so it can be deployed with the constructor arguments of `deploy_account`.
+pub const ACCOUNT_CONSTRUCTOR_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x3a, 0x0a, 0x60, 0x03, 0x7e, 0x7e, 0x7e,
+    0x01, 0x7e, 0x60, 0x01, 0x7e, 0x01, 0x7e, 0x60, 0x03, 0x7f, 0x7f, 0x7f, 0x00, 0x60, 0x02, 0x7f,
+    0x7f, 0x01, 0x7e, 0x60, 0x02, 0x7f, 0x7e, 0x00, 0x60, 0x00, 0x00, 0x60, 0x04, 0x7f, 0x7e, 0x7f,
+    0x7f, 0x01, 0x7e, 0x60, 0x02, 0x7f, 0x7e, 0x01, 0x7e, 0x60, 0x01, 0x7e, 0x01, 0x7f, 0x60, 0x02,
+    0x7e, 0x7e, 0x01, 0x7e, 0x02, 0x0d, 0x02, 0x01, 0x76, 0x01, 0x68, 0x00, 0x00, 0x01, 0x62, 0x01,
+    0x38, 0x00, 0x01, 0x03, 0x0e, 0x0d, 0x02, 0x02, 0x03, 0x00, 0x00, 0x04, 0x02, 0x05, 0x06, 0x06,
+    0x07, 0x08, 0x09, 0x04, 0x05, 0x01, 0x70, 0x01, 0x01, 0x01, 0x05, 0x03, 0x01, 0x00, 0x11, 0x06,
+    0x19, 0x03, 0x7f, 0x01, 0x41, 0x80, 0x80, 0xc0, 0x00, 0x0b, 0x7f, 0x00, 0x41, 0xd0, 0x80, 0xc0,
+    0x00, 0x0b, 0x7f, 0x00, 0x41, 0xd0, 0x80, 0xc0, 0x00, 0x0b, 0x07, 0x48, 0x06, 0x06, 0x6d, 0x65,
+    0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, 0x0c, 0x5f, 0x5f, 0x63, 0x68, 0x65, 0x63, 0x6b, 0x5f, 0x61,
+    0x75, 0x74, 0x68, 0x00, 0x06, 0x01, 0x5f, 0x00, 0x09, 0x0a, 0x5f, 0x5f, 0x64, 0x61, 0x74, 0x61,
+    0x5f, 0x65, 0x6e, 0x64, 0x03, 0x01, 0x0b, 0x5f, 0x5f, 0x68, 0x65, 0x61, 0x70, 0x5f, 0x62, 0x61,
+    0x73, 0x65, 0x03, 0x02, 0x0d, 0x5f, 0x5f, 0x63, 0x6f, 0x6e, 0x73, 0x74, 0x72, 0x75, 0x63, 0x74,
+    0x6f, 0x72, 0x00, 0x0e, 0x0a, 0xef, 0x04, 0x0d, 0x86, 0x01, 0x02, 0x01, 0x7f, 0x01, 0x7e, 0x23,
+    0x80, 0x80, 0x80, 0x80, 0x00, 0x41, 0x10, 0x6b, 0x22, 0x03, 0x24, 0x80, 0x80, 0x80, 0x80, 0x00,
+    0x02, 0x40, 0x02, 0x40, 0x20, 0x02, 0x29, 0x03, 0x00, 0x22, 0x04, 0x42, 0xff, 0x01, 0x83, 0x42,
+    0xcb, 0x00, 0x51, 0x0d, 0x00, 0x20, 0x00, 0x42, 0x01, 0x37, 0x03, 0x00, 0x0c, 0x01, 0x0b, 0x20,
+    0x03, 0x42, 0x02, 0x37, 0x03, 0x08, 0x20, 0x01, 0x20, 0x04, 0x20, 0x03, 0x41, 0x08, 0x6a, 0x41,
+    0x01, 0x10, 0x8a, 0x80, 0x80, 0x80, 0x00, 0x1a, 0x02, 0x40, 0x20, 0x03, 0x29, 0x03, 0x08, 0x22,
+    0x04, 0x42, 0xff, 0x01, 0x83, 0x42, 0xcc, 0x00, 0x51, 0x0d, 0x00, 0x20, 0x00, 0x42, 0x01, 0x37,
+    0x03, 0x00, 0x0c, 0x01, 0x0b, 0x20, 0x00, 0x42, 0x00, 0x37, 0x03, 0x00, 0x20, 0x00, 0x20, 0x04,
+    0x37, 0x03, 0x08, 0x0b, 0x20, 0x03, 0x41, 0x10, 0x6a, 0x24, 0x80, 0x80, 0x80, 0x80, 0x00, 0x0b,
+    0x51, 0x02, 0x01, 0x7f, 0x01, 0x7e, 0x23, 0x80, 0x80, 0x80, 0x80, 0x00, 0x41, 0x10, 0x6b, 0x22,
+    0x03, 0x24, 0x80, 0x80, 0x80, 0x80, 0x00, 0x20, 0x03, 0x20, 0x01, 0x20, 0x02, 0x10, 0x88, 0x80,
+    0x80, 0x80, 0x00, 0x42, 0x01, 0x21, 0x04, 0x02, 0x40, 0x20, 0x03, 0x28, 0x02, 0x00, 0x0d, 0x00,
+    0x20, 0x00, 0x20, 0x03, 0x29, 0x03, 0x08, 0x37, 0x03, 0x08, 0x42, 0x00, 0x21, 0x04, 0x0b, 0x20,
+    0x00, 0x20, 0x04, 0x37, 0x03, 0x00, 0x20, 0x03, 0x41, 0x10, 0x6a, 0x24, 0x80, 0x80, 0x80, 0x80,
+    0x00, 0x0b, 0x14, 0x00, 0x20, 0x01, 0x28, 0x02, 0x00, 0x41, 0x03, 0x74, 0x41, 0x80, 0x80, 0xc0,
+    0x80, 0x00, 0x6a, 0x29, 0x03, 0x00, 0x0b, 0x95, 0x01, 0x01, 0x01, 0x7f, 0x23, 0x80, 0x80, 0x80,
+    0x80, 0x00, 0x41, 0x30, 0x6b, 0x22, 0x03, 0x24, 0x80, 0x80, 0x80, 0x80, 0x00, 0x20, 0x03, 0x20,
+    0x01, 0x37, 0x03, 0x10, 0x20, 0x03, 0x20, 0x00, 0x37, 0x03, 0x08, 0x20, 0x03, 0x41, 0x18, 0x6a,
+    0x20, 0x03, 0x41, 0x2f, 0x6a, 0x20, 0x03, 0x41, 0x08, 0x6a, 0x10, 0x83, 0x80, 0x80, 0x80, 0x00,
+    0x02, 0x40, 0x20, 0x03, 0x28, 0x02, 0x18, 0x41, 0x01, 0x46, 0x0d, 0x00, 0x20, 0x03, 0x41, 0x18,
+    0x6a, 0x20, 0x03, 0x41, 0x2f, 0x6a, 0x20, 0x03, 0x41, 0x10, 0x6a, 0x10, 0x82, 0x80, 0x80, 0x80,
+    0x00, 0x20, 0x03, 0x28, 0x02, 0x18, 0x41, 0x01, 0x46, 0x0d, 0x00, 0x20, 0x02, 0x42, 0xff, 0x01,
+    0x83, 0x42, 0xcb, 0x00, 0x52, 0x0d, 0x00, 0x20, 0x03, 0x41, 0x00, 0x36, 0x02, 0x18, 0x20, 0x03,
+    0x20, 0x03, 0x41, 0x18, 0x6a, 0x10, 0x84, 0x80, 0x80, 0x80, 0x00, 0x21, 0x01, 0x20, 0x03, 0x41,
+    0x30, 0x6a, 0x24, 0x80, 0x80, 0x80, 0x80, 0x00, 0x20, 0x01, 0x0f, 0x0b, 0x00, 0x0b, 0x14, 0x00,
+    0x10, 0x89, 0x80, 0x80, 0x80, 0x00, 0x20, 0x00, 0x20, 0x01, 0x20, 0x02, 0x10, 0x85, 0x80, 0x80,
+    0x80, 0x00, 0x0b, 0x5d, 0x02, 0x01, 0x7f, 0x01, 0x7e, 0x23, 0x80, 0x80, 0x80, 0x80, 0x00, 0x41,
+    0x10, 0x6b, 0x22, 0x02, 0x24, 0x80, 0x80, 0x80, 0x80, 0x00, 0x20, 0x02, 0x20, 0x01, 0x37, 0x03,
+    0x08, 0x42, 0x01, 0x21, 0x03, 0x02, 0x40, 0x20, 0x02, 0x41, 0x10, 0x6a, 0x20, 0x01, 0x10, 0x8c,
+    0x80, 0x80, 0x80, 0x00, 0x10, 0x8d, 0x80, 0x80, 0x80, 0x00, 0x41, 0x20, 0x47, 0x0d, 0x00, 0x20,
+    0x00, 0x20, 0x02, 0x29, 0x03, 0x08, 0x37, 0x03, 0x08, 0x42, 0x00, 0x21, 0x03, 0x0b, 0x20, 0x00,
+    0x20, 0x03, 0x37, 0x03, 0x00, 0x20, 0x02, 0x41, 0x10, 0x6a, 0x24, 0x80, 0x80, 0x80, 0x80, 0x00,
+    0x0b, 0x2a, 0x01, 0x01, 0x7e, 0x02, 0x40, 0x20, 0x02, 0x29, 0x03, 0x00, 0x22, 0x03, 0x42, 0xff,
+    0x01, 0x83, 0x42, 0xc8, 0x00, 0x51, 0x0d, 0x00, 0x20, 0x00, 0x42, 0x01, 0x37, 0x03, 0x00, 0x0f,
+    0x0b, 0x20, 0x00, 0x20, 0x03, 0x10, 0x87, 0x80, 0x80, 0x80, 0x00, 0x0b, 0x02, 0x00, 0x0b, 0x10,
+    0x00, 0x20, 0x00, 0x20, 0x01, 0x20, 0x02, 0x20, 0x03, 0x10, 0x8b, 0x80, 0x80, 0x80, 0x00, 0x0b,
+    0x1c, 0x00, 0x20, 0x01, 0x20, 0x02, 0xad, 0x42, 0x20, 0x86, 0x42, 0x04, 0x84, 0x20, 0x03, 0xad,
+    0x42, 0x20, 0x86, 0x42, 0x04, 0x84, 0x10, 0x80, 0x80, 0x80, 0x80, 0x00, 0x0b, 0x0a, 0x00, 0x20,
+    0x01, 0x10, 0x81, 0x80, 0x80, 0x80, 0x00, 0x0b, 0x08, 0x00, 0x20, 0x00, 0x42, 0x20, 0x88, 0xa7,
+    0x0b, 0x04, 0x00, 0x42, 0x02, 0x0b, 0x0b, 0x59, 0x01, 0x00, 0x41, 0x80, 0x80, 0xc0, 0x00, 0x0b,
+    0x50, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+    0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00,
+    0x00, 0x03, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00,
+    0x00, 0x03, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00,
+    0x00, 0x03, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00,
+    0x00, 0x00, 0xb7, 0x01, 0x0e, 0x63, 0x6f, 0x6e, 0x74, 0x72, 0x61, 0x63, 0x74, 0x73, 0x70, 0x65,
+    0x63, 0x76, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x5f,
+    0x5f, 0x63, 0x68, 0x65, 0x63, 0x6b, 0x5f, 0x61, 0x75, 0x74, 0x68, 0x00, 0x00, 0x00, 0x03, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0x5f, 0x73, 0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72,
+    0x65, 0x5f, 0x70, 0x61, 0x79, 0x6c, 0x6f, 0x61, 0x64, 0x00, 0x00, 0x00, 0x00, 0x03, 0xee, 0x00,
+    0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0b, 0x5f, 0x73, 0x69, 0x67, 0x6e,
+    0x61, 0x74, 0x75, 0x72, 0x65, 0x73, 0x00, 0x00, 0x00, 0x07, 0xd0, 0x00, 0x00, 0x00, 0x0a, 0x53,
+    0x69, 0x67, 0x6e, 0x61, 0x74, 0x75, 0x72, 0x65, 0x73, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x0e, 0x5f, 0x61, 0x75, 0x74, 0x68, 0x5f, 0x63, 0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74,
+    0x73, 0x00, 0x00, 0x00, 0x00, 0x03, 0xea, 0x00, 0x00, 0x07, 0xd0, 0x00, 0x00, 0x00, 0x07, 0x43,
+    0x6f, 0x6e, 0x74, 0x65, 0x78, 0x74, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x03, 0xe9, 0x00,
+    0x00, 0x03, 0xed, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x1e, 0x11, 0x63, 0x6f,
+    0x6e, 0x74, 0x72, 0x61, 0x63, 0x74, 0x65, 0x6e, 0x76, 0x6d, 0x65, 0x74, 0x61, 0x76, 0x30, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x16, 0x00, 0x00, 0x00, 0x00, 0x00, 0x6f, 0x0e, 0x63, 0x6f,
+    0x6e, 0x74, 0x72, 0x61, 0x63, 0x74, 0x6d, 0x65, 0x74, 0x61, 0x76, 0x30, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x05, 0x72, 0x73, 0x76, 0x65, 0x72, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06,
+    0x31, 0x2e, 0x38, 0x38, 0x2e, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08,
+    0x72, 0x73, 0x73, 0x64, 0x6b, 0x76, 0x65, 0x72, 0x00, 0x00, 0x00, 0x2f, 0x32, 0x32, 0x2e, 0x30,
+    0x2e, 0x38, 0x23, 0x66, 0x34, 0x36, 0x65, 0x39, 0x65, 0x30, 0x36, 0x31, 0x30, 0x32, 0x31, 0x33,
+    0x62, 0x62, 0x62, 0x37, 0x32, 0x32, 0x38, 0x35, 0x35, 0x36, 0x36, 0x66, 0x39, 0x64, 0x64, 0x39,
+    0x36, 0x30, 0x66, 0x66, 0x39, 0x36, 0x64, 0x30, 0x33, 0x64, 0x38, 0x00,
+];
@@ -0,0 +1,341 @@
+#![no_std]
+//! Tamper-evident audit log plugin for the smart account.
+//!
+//! Every authorization of an account that installs the plugin appends an entry to a hash
+//! chain kept in the plugin's storage. Each entry commits to the hash of the previous entry,
+//! the hash of the authorized contexts, the ledger timestamp and the keys of the signers
+//! that authorized it, so removing or altering any entry changes every hash after it.
+//!
+//! Entries are also published as events. The [`verifier`] module replays them off-chain and
+//! checks them against the on-chain `head()`, which proves that no authorization is missing
+//! from the replayed history even when RPC providers have pruned older events.
+//!
+//! Calls an executor plugin makes through `execute_from_plugin` are logged as well, with the
+//! executor's address in place of signer keys.
+//!
+//! **Not logged:** `emergency_uninstall_plugin`. Authorizing it skips every plugin hook, so
+//! that a faulty plugin cannot block its own removal, and the audit log is no exception.
+//! The account still publishes a `PluginUninstalledEvent` for it, but that event is not part
+//! of the chain. Monitor those events alongside the chain if emergency removals matter.
+use smart_account_interfaces::{PluginHook, PluginHooks, SignerAwarePlugin, SmartAccountPlugin};
+use soroban_sdk::{
+    auth::Context, contract, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, vec, Address, BytesN, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+pub mod verifier;
+
+pub const TOPIC_AUDIT: Symbol = symbol_short!("audit");
+
+// Persistent entries are extended to 60 days once less than 14 days are left, matching the
+// smart account's own storage. Reading the chain through `head` or `entries` extends it too.
+const DAY_IN_LEDGERS: u32 = 17_280;
+const TTL_THRESHOLD: u32 = 14 * DAY_IN_LEDGERS;
+const TTL_EXTEND_TO: u32 = 60 * DAY_IN_LEDGERS;
+
+/// Maximum number of entries returned by a single `entries()` call.
+pub const MAX_ENTRIES_PAGE: u64 = 50;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AuditError {
+    /// The account has never installed the plugin
+    NotConfigured = 1,
+    /// The requested range is reversed, out of bounds or larger than a page
+    InvalidRange = 2,
+}
+
+/// One link of an account's audit chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditEntry {
+    /// Position of the entry in the chain, starting at 0
+    pub index: u64,
+    /// Hash of the previous entry, or all zeroes for the first entry
+    pub prev_hash: BytesN<32>,
+    /// SHA-256 of the XDR-encoded authorized contexts
+    pub contexts_hash: BytesN<32>,
+    pub timestamp: u64,
+    /// Signer keys that authorized the transaction, as defined by the smart account
+    pub signers: Vec<Val>,
+}
+
+/// Latest state of an account's audit chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditHead {
+    /// Number of entries in the chain
+    pub length: u64,
+    /// Hash of the last entry, or all zeroes for an empty chain
+    pub hash: BytesN<32>,
+}
+
+impl AuditHead {
+    pub fn genesis(env: &Env) -> Self {
+        AuditHead {
+            length: 0,
+            hash: BytesN::from_array(env, &[0; 32]),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Head(Address),
+    Entry(Address, u64),
+}
+
+#[contract]
+pub struct AuditLogPlugin;
+
+#[contractimpl]
+impl PluginHooks for AuditLogPlugin {
+    /// Subscribes to `SignedAuth` instead of `Auth`, since entries record the signer keys.
+    fn hooks(env: &Env) -> Vec<PluginHook> {
+        vec![
+            env,
+            PluginHook::Install,
+            PluginHook::Uninstall,
+            PluginHook::SignedAuth,
+        ]
+    }
+}
+
+#[contractimpl]
+impl SmartAccountPlugin for AuditLogPlugin {
+    /// Starts the account's chain. Reinstalling the plugin continues the existing chain.
+    fn on_install(env: &Env, source: Address) {
+        source.require_auth();
+        let key = DataKey::Head(source);
+        if load::<AuditHead>(env, &key).is_none() {
+            store(env, &key, &AuditHead::genesis(env));
+        }
+    }
+
+    /// Keeps the chain, so that uninstalling the plugin cannot erase the account's history.
+    fn on_uninstall(_env: &Env, source: Address) {
+        source.require_auth();
+    }
+
+    fn on_auth(_env: &Env, _source: Address, _contexts: Vec<Context>) {}
+}
+
+#[contractimpl]
+impl SignerAwarePlugin for AuditLogPlugin {
+    /// Appends an entry for the authorization to the account's chain.
+    fn on_signed_auth(env: &Env, source: Address, contexts: Vec<Context>, signers: Vec<Val>) {
+        source.require_auth();
+        let head = Self::head(env, source.clone());
+
+        let entry = AuditEntry {
+            index: head.length,
+            prev_hash: head.hash,
+            contexts_hash: verifier::contexts_hash(env, &contexts),
+            timestamp: env.ledger().timestamp(),
+            signers,
+        };
+        let head = AuditHead {
+            length: head.length + 1,
+            hash: verifier::entry_hash(env, &entry),
+        };
+
+        store(env, &DataKey::Entry(source.clone(), entry.index), &entry);
+        store(env, &DataKey::Head(source.clone()), &head);
+
+        env.events().publish((TOPIC_AUDIT, source), entry);
+    }
+}
+
+#[contractimpl]
+impl AuditLogPlugin {
+    /// Returns the length and latest hash of the account's chain.
+    pub fn head(env: &Env, account: Address) -> AuditHead {
+        load(env, &DataKey::Head(account))
+            .unwrap_or_else(|| panic_with_error!(env, AuditError::NotConfigured))
+    }
+
+    /// Returns the entries with an index in `[start, end)`, at most `MAX_ENTRIES_PAGE` of them.
+    pub fn entries(env: &Env, account: Address, start: u64, end: u64) -> Vec<AuditEntry> {
+        let head = Self::head(env, account.clone());
+        if start > end || end > head.length || end - start > MAX_ENTRIES_PAGE {
+            panic_with_error!(env, AuditError::InvalidRange);
+        }
+
+        let mut entries = Vec::new(env);
+        for index in start..end {
+            let entry: AuditEntry = load(env, &DataKey::Entry(account.clone(), index)).unwrap();
+            entries.push_back(entry);
+        }
+        entries
+    }
+}
+
+/// Reads a persistent entry and extends its TTL, so that the chain of an account that has
+/// been idle for a long time can still be verified and extended.
+fn load<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+    let value = env.storage().persistent().get(key)?;
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    Some(value)
+}
+
+fn store<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+#[cfg(test)]
+mod test {
+    use super::verifier::{entries_from_events, verify_chain, VerifyError};
+    use super::*;
+    use soroban_sdk::{
+        auth::ContractContext,
+        testutils::{storage::Persistent as _, Address as _, Events as _, Ledger as _},
+        IntoVal,
+    };
+
+    fn setup() -> (Env, AuditLogPluginClient<'static>, Address) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(AuditLogPlugin, ());
+        let client = AuditLogPluginClient::new(&env, &contract_id);
+        let account = Address::generate(&env);
+        client.on_install(&account);
+        (env, client, account)
+    }
+
+    fn record(env: &Env, client: &AuditLogPluginClient, account: &Address, signer: u8) {
+        let contexts = vec![
+            env,
+            Context::Contract(ContractContext {
+                contract: Address::generate(env),
+                fn_name: symbol_short!("transfer"),
+                args: vec![env, (signer as u32).into_val(env)],
+            }),
+        ];
+        let signers: Vec<Val> = vec![env, BytesN::from_array(env, &[signer; 32]).into_val(env)];
+        client.on_signed_auth(account, &contexts, &signers);
+    }
+
+    #[test]
+    fn test_replayed_events_match_head() {
+        let (env, client, account) = setup();
+        let mut entries = Vec::new(&env);
+        for signer in 1..=3 {
+            record(&env, &client, &account, signer);
+            // Replay after each call, as the test environment keeps the latest invocation's
+            // events only
+            for entry in entries_from_events(&env, &env.events().all(), &client.address, &account) {
+                if entry.index == entries.len() as u64 {
+                    entries.push_back(entry);
+                }
+            }
+        }
+
+        let head = client.head(&account);
+        assert_eq!(head.length, 3);
+        assert_eq!(client.entries(&account, &0, &3), entries);
+        assert_eq!(
+            verify_chain(&env, &AuditHead::genesis(&env), &entries, &head),
+            Ok(())
+        );
+
+        // Verification can also resume from an intermediate checkpoint
+        let checkpoint = AuditHead {
+            length: 1,
+            hash: verifier::entry_hash(&env, &entries.get(0).unwrap()),
+        };
+        assert_eq!(
+            verify_chain(&env, &checkpoint, &entries.slice(1..), &head),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_verifier_detects_tampering() {
+        let (env, client, account) = setup();
+        for signer in 1..=3 {
+            record(&env, &client, &account, signer);
+        }
+        let head = client.head(&account);
+        let genesis = AuditHead::genesis(&env);
+        let entries = client.entries(&account, &0, &3);
+
+        // A hidden authorization breaks the chain
+        let mut hidden = entries.clone();
+        hidden.remove(1);
+        assert_eq!(
+            verify_chain(&env, &genesis, &hidden, &head),
+            Err(VerifyError::IndexMismatch(1))
+        );
+
+        // An altered entry no longer matches the hash committed by its successor
+        let mut altered = entries.clone();
+        let mut entry = altered.get(1).unwrap();
+        entry.signers = vec![&env, BytesN::from_array(&env, &[9; 32]).into_val(&env)];
+        altered.set(1, entry);
+        assert_eq!(
+            verify_chain(&env, &genesis, &altered, &head),
+            Err(VerifyError::BrokenLink(2))
+        );
+
+        // Dropping the most recent entries is caught by the on-chain head
+        assert_eq!(
+            verify_chain(&env, &genesis, &entries.slice(0..2), &head),
+            Err(VerifyError::HeadMismatch)
+        );
+    }
+
+    #[test]
+    fn test_entries_rejects_invalid_range() {
+        let (env, client, account) = setup();
+        record(&env, &client, &account, 1);
+
+        assert_eq!(
+            client.try_entries(&account, &0, &2).unwrap_err().unwrap(),
+            AuditError::InvalidRange.into()
+        );
+        assert_eq!(
+            client.try_entries(&account, &1, &0).unwrap_err().unwrap(),
+            AuditError::InvalidRange.into()
+        );
+        assert_eq!(client.entries(&account, &1, &1).len(), 0);
+    }
+
+    #[test]
+    fn test_reading_the_chain_extends_its_ttl() {
+        let (env, client, account) = setup();
+        record(&env, &client, &account, 1);
+        let ttl = |key: &DataKey| {
+            env.as_contract(&client.address, || env.storage().persistent().get_ttl(key))
+        };
+        let head_key = DataKey::Head(account.clone());
+        let entry_key = DataKey::Entry(account.clone(), 0);
+        assert_eq!(ttl(&head_key), TTL_EXTEND_TO);
+        assert_eq!(ttl(&entry_key), TTL_EXTEND_TO);
+
+        // An account idle for long enough to reach the threshold is extended on read
+        env.ledger().with_mut(|li| {
+            li.sequence_number += TTL_EXTEND_TO - TTL_THRESHOLD + 1;
+        });
+        client.entries(&account, &0, &1);
+        assert_eq!(ttl(&head_key), TTL_EXTEND_TO);
+        assert_eq!(ttl(&entry_key), TTL_EXTEND_TO);
+    }
+
+    #[test]
+    fn test_reinstall_keeps_chain() {
+        let (env, client, account) = setup();
+        record(&env, &client, &account, 1);
+        let head = client.head(&account);
+
+        client.on_uninstall(&account);
+        client.on_install(&account);
+        assert_eq!(client.head(&account), head);
+    }
+}
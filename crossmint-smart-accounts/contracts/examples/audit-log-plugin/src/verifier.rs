@@ -0,0 +1,78 @@
+//! Replays an account's audit chain and checks it against an on-chain head.
+//!
+//! The functions only need an [`Env`], so they run unchanged off-chain: decode the
+//! `audit` events returned by an RPC provider (or the entries returned by `entries()`)
+//! with a host environment, then call [`verify_chain`] with the head read from the plugin.
+//! A chain that verifies against the current `head()` contains every authorization the
+//! account performed since the checkpoint it starts from.
+use crate::{AuditEntry, AuditHead, TOPIC_AUDIT};
+use soroban_sdk::{auth::Context, xdr::ToXdr, Address, BytesN, Env, IntoVal, TryFromVal, Val, Vec};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifyError {
+    /// The entry at this position does not carry the expected index, so an entry is
+    /// missing or duplicated
+    IndexMismatch(u64),
+    /// The entry at this index does not commit to the hash of its predecessor
+    BrokenLink(u64),
+    /// The replayed chain does not end at the expected head
+    HeadMismatch,
+}
+
+/// Hash committed to by the entry that follows `entry`.
+pub fn entry_hash(env: &Env, entry: &AuditEntry) -> BytesN<32> {
+    env.crypto().sha256(&entry.clone().to_xdr(env)).into()
+}
+
+/// Hash of the authorized contexts recorded in an entry.
+pub fn contexts_hash(env: &Env, contexts: &Vec<Context>) -> BytesN<32> {
+    env.crypto().sha256(&contexts.clone().to_xdr(env)).into()
+}
+
+/// Extracts the entries of `account`'s chain from events published by `plugin`, in order.
+pub fn entries_from_events(
+    env: &Env,
+    events: &Vec<(Address, Vec<Val>, Val)>,
+    plugin: &Address,
+    account: &Address,
+) -> Vec<AuditEntry> {
+    let topics: Vec<Val> = (TOPIC_AUDIT, account.clone()).into_val(env);
+    let mut entries = Vec::new(env);
+    for (contract, event_topics, data) in events.iter() {
+        if contract != *plugin || event_topics != topics {
+            continue;
+        }
+        if let Ok(entry) = AuditEntry::try_from_val(env, &data) {
+            entries.push_back(entry);
+        }
+    }
+    entries
+}
+
+/// Replays `entries` on top of the `from` checkpoint and checks that they lead to `to`.
+///
+/// Use `AuditHead::genesis` as the checkpoint to verify a chain from its start.
+pub fn verify_chain(
+    env: &Env,
+    from: &AuditHead,
+    entries: &Vec<AuditEntry>,
+    to: &AuditHead,
+) -> Result<(), VerifyError> {
+    let mut head = from.clone();
+    for entry in entries.iter() {
+        if entry.index != head.length {
+            return Err(VerifyError::IndexMismatch(head.length));
+        }
+        if entry.prev_hash != head.hash {
+            return Err(VerifyError::BrokenLink(entry.index));
+        }
+        head = AuditHead {
+            length: head.length + 1,
+            hash: entry_hash(env, &entry),
+        };
+    }
+    if head != *to {
+        return Err(VerifyError::HeadMismatch);
+    }
+    Ok(())
+}
@@ -0,0 +1,330 @@
+#![no_std]
+//! Reference social-recovery plugin for the smart account.
+//!
+//! Each account that installs the plugin configures a set of guardians, an approval
+//! threshold and a delay. Guardians propose and approve a rotation of the account's admin
+//! signers through their own `require_auth`. Once the threshold is reached and the delay
+//! has elapsed, anyone can execute the rotation, which the plugin relays to the account's
+//! privileged `rotate_admins` callback. The account's current admins can cancel a pending
+//! proposal at any time before it executes.
+use smart_account_interfaces::{
+    ConfigurablePlugin, PluginHook, PluginHooks, RecoverableAccountClient, SmartAccountPlugin,
+};
+use soroban_sdk::{
+    auth::Context, contract, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, vec, xdr::FromXdr, Address, Bytes, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+};
+
+const TOPIC_RECOVERY: Symbol = symbol_short!("recovery");
+
+// Persistent entries are extended to 60 days once less than 14 days are left, matching the
+// smart account's own storage
+const DAY_IN_LEDGERS: u32 = 17_280;
+const TTL_THRESHOLD: u32 = 14 * DAY_IN_LEDGERS;
+const TTL_EXTEND_TO: u32 = 60 * DAY_IN_LEDGERS;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RecoveryError {
+    /// The account has not installed the plugin
+    NotConfigured = 1,
+    /// Config is not a valid XDR-encoded `RecoveryConfig`
+    InvalidConfig = 2,
+    /// Caller is not a guardian of the account
+    NotGuardian = 3,
+    /// A proposal is already pending for the account
+    ProposalPending = 4,
+    /// No proposal is pending for the account
+    NoProposal = 5,
+    /// Guardian already approved the pending proposal
+    AlreadyApproved = 6,
+    /// Not enough guardians approved the pending proposal
+    ThresholdNotReached = 7,
+    /// The recovery delay has not elapsed yet
+    DelayNotElapsed = 8,
+}
+
+/// Per-account recovery settings, passed XDR-encoded as the plugin install config.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryConfig {
+    pub guardians: Vec<Address>,
+    /// Number of guardian approvals required to execute a proposal
+    pub threshold: u32,
+    /// Seconds between reaching the threshold and being able to execute
+    pub delay: u64,
+}
+
+/// A pending rotation of the account's admin signers.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryProposal {
+    /// Signer keys of the admins to remove, as defined by the smart account
+    pub remove: Vec<Val>,
+    /// Admin signers to add, as defined by the smart account
+    pub add: Vec<Val>,
+    pub approvals: Vec<Address>,
+    /// Ledger timestamp at which the threshold was reached
+    pub approved_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Config(Address),
+    Proposal(Address),
+}
+
+#[contract]
+pub struct SocialRecoveryPlugin;
+
+#[contractimpl]
+impl PluginHooks for SocialRecoveryPlugin {
+    /// Subscribes to the lifecycle hooks only, so the plugin adds no cost to `__check_auth`.
+    fn hooks(env: &Env) -> Vec<PluginHook> {
+        vec![
+            env,
+            PluginHook::Install,
+            PluginHook::Uninstall,
+            PluginHook::Config,
+        ]
+    }
+}
+
+#[contractimpl]
+impl SmartAccountPlugin for SocialRecoveryPlugin {
+    /// The plugin is useless without guardians, so it refuses to be installed without a
+    /// config. Accounts call `on_install_with_config` instead since it subscribes to `Config`.
+    fn on_install(env: &Env, _source: Address) {
+        panic_with_error!(env, RecoveryError::InvalidConfig);
+    }
+
+    fn on_uninstall(env: &Env, source: Address) {
+        source.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Config(source.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Proposal(source));
+    }
+
+    fn on_auth(_env: &Env, _source: Address, _contexts: Vec<Context>) {}
+}
+
+#[contractimpl]
+impl ConfigurablePlugin for SocialRecoveryPlugin {
+    fn on_install_with_config(env: &Env, source: Address, config: Bytes) {
+        source.require_auth();
+        let config = decode_config(env, &config);
+        store(env, &DataKey::Config(source), &config);
+    }
+
+    fn on_config(env: &Env, source: Address, config: Bytes) {
+        source.require_auth();
+        load_config(env, &source);
+        let config = decode_config(env, &config);
+        // Approvals were given under the previous guardian set
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Proposal(source.clone()));
+        store(env, &DataKey::Config(source), &config);
+    }
+}
+
+#[contractimpl]
+impl SocialRecoveryPlugin {
+    /// Proposes a rotation of the account's admins. Counts as the proposer's approval.
+    pub fn propose(
+        env: &Env,
+        account: Address,
+        guardian: Address,
+        remove: Vec<Val>,
+        add: Vec<Val>,
+    ) {
+        guardian.require_auth();
+        let config = load_config(env, &account);
+        require_guardian(env, &config, &guardian);
+
+        let key = DataKey::Proposal(account.clone());
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(env, RecoveryError::ProposalPending);
+        }
+        let mut proposal = RecoveryProposal {
+            remove,
+            add,
+            approvals: vec![env, guardian],
+            approved_at: None,
+        };
+        mark_if_approved(env, &config, &mut proposal);
+        store(env, &key, &proposal);
+
+        env.events()
+            .publish((TOPIC_RECOVERY, symbol_short!("proposed")), account);
+    }
+
+    /// Adds a guardian's approval to the pending proposal.
+    pub fn approve(env: &Env, account: Address, guardian: Address) {
+        guardian.require_auth();
+        let config = load_config(env, &account);
+        require_guardian(env, &config, &guardian);
+
+        let mut proposal = load_proposal(env, &account);
+        if proposal.approvals.contains(&guardian) {
+            panic_with_error!(env, RecoveryError::AlreadyApproved);
+        }
+        proposal.approvals.push_back(guardian);
+        mark_if_approved(env, &config, &mut proposal);
+        store(env, &DataKey::Proposal(account.clone()), &proposal);
+
+        env.events()
+            .publish((TOPIC_RECOVERY, symbol_short!("approved")), account);
+    }
+
+    /// Cancels the pending proposal. Requires the account's own authorization, so that its
+    /// current admins can veto a recovery during the delay.
+    pub fn cancel(env: &Env, account: Address) {
+        account.require_auth();
+        load_proposal(env, &account);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Proposal(account.clone()));
+
+        env.events()
+            .publish((TOPIC_RECOVERY, symbol_short!("cancelled")), account);
+    }
+
+    /// Executes an approved proposal once the delay has elapsed. Callable by anyone.
+    pub fn execute(env: &Env, account: Address) {
+        let config = load_config(env, &account);
+        let proposal = load_proposal(env, &account);
+        let Some(approved_at) = proposal.approved_at else {
+            panic_with_error!(env, RecoveryError::ThresholdNotReached);
+        };
+        if env.ledger().timestamp() < approved_at.saturating_add(config.delay) {
+            panic_with_error!(env, RecoveryError::DelayNotElapsed);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Proposal(account.clone()));
+        RecoverableAccountClient::new(env, &account).rotate_admins(
+            &env.current_contract_address(),
+            &proposal.remove,
+            &proposal.add,
+        );
+
+        env.events()
+            .publish((TOPIC_RECOVERY, symbol_short!("executed")), account);
+    }
+
+    pub fn get_config(env: &Env, account: Address) -> Option<RecoveryConfig> {
+        load(env, &DataKey::Config(account))
+    }
+
+    pub fn get_proposal(env: &Env, account: Address) -> Option<RecoveryProposal> {
+        load(env, &DataKey::Proposal(account))
+    }
+}
+
+fn decode_config(env: &Env, config: &Bytes) -> RecoveryConfig {
+    let Ok(config) = RecoveryConfig::from_xdr(env, config) else {
+        panic_with_error!(env, RecoveryError::InvalidConfig);
+    };
+    let guardian_count = config.guardians.len();
+    let has_duplicates = config
+        .guardians
+        .iter()
+        .enumerate()
+        .any(|(i, guardian)| config.guardians.first_index_of(&guardian) != Some(i as u32));
+    if config.threshold == 0 || config.threshold > guardian_count || has_duplicates {
+        panic_with_error!(env, RecoveryError::InvalidConfig);
+    }
+    config
+}
+
+/// Reads a persistent entry and extends its TTL, so that guardians can still recover an
+/// account that has been idle for a long time.
+fn load<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+    let value = env.storage().persistent().get(key)?;
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    Some(value)
+}
+
+fn store<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+fn load_config(env: &Env, account: &Address) -> RecoveryConfig {
+    load(env, &DataKey::Config(account.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, RecoveryError::NotConfigured))
+}
+
+fn load_proposal(env: &Env, account: &Address) -> RecoveryProposal {
+    load(env, &DataKey::Proposal(account.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, RecoveryError::NoProposal))
+}
+
+fn require_guardian(env: &Env, config: &RecoveryConfig, guardian: &Address) {
+    if !config.guardians.contains(guardian) {
+        panic_with_error!(env, RecoveryError::NotGuardian);
+    }
+}
+
+/// Starts the delay when the proposal reaches the threshold.
+fn mark_if_approved(env: &Env, config: &RecoveryConfig, proposal: &mut RecoveryProposal) {
+    if proposal.approved_at.is_none() && proposal.approvals.len() >= config.threshold {
+        proposal.approved_at = Some(env.ledger().timestamp());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, xdr::ToXdr};
+
+    #[test]
+    fn test_rejects_threshold_above_guardian_count() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(SocialRecoveryPlugin, ());
+        let client = SocialRecoveryPluginClient::new(&env, &contract_id);
+
+        let config = RecoveryConfig {
+            guardians: vec![&env, Address::generate(&env)],
+            threshold: 2,
+            delay: 0,
+        };
+        let res = client.try_on_install_with_config(&Address::generate(&env), &config.to_xdr(&env));
+        assert_eq!(
+            res.unwrap_err().unwrap(),
+            RecoveryError::InvalidConfig.into()
+        );
+    }
+
+    #[test]
+    fn test_rejects_duplicate_guardians() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(SocialRecoveryPlugin, ());
+        let client = SocialRecoveryPluginClient::new(&env, &contract_id);
+
+        let guardian = Address::generate(&env);
+        let config = RecoveryConfig {
+            guardians: vec![&env, guardian.clone(), guardian],
+            threshold: 2,
+            delay: 0,
+        };
+        let res = client.try_on_install_with_config(&Address::generate(&env), &config.to_xdr(&env));
+        assert_eq!(
+            res.unwrap_err().unwrap(),
+            RecoveryError::InvalidConfig.into()
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol, Val, Vec};
+
+/// A contract invocation an executor plugin asks the smart account to perform.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutorCall {
+    pub contract: Address,
+    pub fn_name: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// Entry point through which installed executor plugins act on behalf of a smart account.
+///
+/// Calls are performed with the account as the direct invoker, so they are authorized as
+/// the account, within the scope an admin granted to the plugin. They bypass the account's
+/// `__check_auth`: the account invokes the auth hooks of its other plugins itself, passing
+/// the executor's address to `on_signed_auth` in place of signer keys.
+#[contractclient(name = "SmartAccountExecutorClient")]
+pub trait SmartAccountExecutor {
+    fn execute_from_plugin(env: &Env, plugin: Address, calls: Vec<ExecutorCall>) -> Vec<Val>;
+}
@@ -1,7 +1,19 @@
 #![no_std]
 
+pub mod executor;
+pub mod oracle;
 pub mod plugin;
 pub mod policy;
+pub mod recovery;
+pub mod release;
 
-pub use plugin::{SmartAccountPlugin, SmartAccountPluginClient};
+pub use executor::{ExecutorCall, SmartAccountExecutor, SmartAccountExecutorClient};
+pub use oracle::{Asset, PriceData, PriceOracle, PriceOracleClient};
+pub use plugin::{
+    ConfigurablePlugin, ConfigurablePluginClient, PluginHook, PluginHooks, PluginHooksClient,
+    PluginInstallParams, SignerAwarePlugin, SignerAwarePluginClient, SmartAccountPlugin,
+    SmartAccountPluginClient,
+};
 pub use policy::{SmartAccountPolicy, SmartAccountPolicyClient};
+pub use recovery::{RecoverableAccount, RecoverableAccountClient};
+pub use release::{ReleaseRegistry, ReleaseRegistryClient};
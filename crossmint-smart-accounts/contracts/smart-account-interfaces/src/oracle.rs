@@ -0,0 +1,30 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+/// Asset identifier as defined by SEP-40.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    /// A Soroban token contract (e.g. a Stellar Asset Contract).
+    Stellar(Address),
+    /// Any other asset, identified by its ticker.
+    Other(Symbol),
+}
+
+/// Price record returned by a SEP-40 oracle.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    /// Price of one unit of the asset in the oracle's base asset, scaled by `decimals()`.
+    pub price: i128,
+    /// Ledger timestamp at which the price was recorded.
+    pub timestamp: u64,
+}
+
+/// Subset of the SEP-40 price feed interface consumed by the smart account.
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn base(env: &Env) -> Asset;
+    fn decimals(env: &Env) -> u32;
+    fn resolution(env: &Env) -> u32;
+    fn lastprice(env: &Env, asset: Asset) -> Option<PriceData>;
+}
@@ -1,8 +1,65 @@
-use soroban_sdk::{auth::Context, contractclient, Address, Env, Vec};
+use soroban_sdk::{auth::Context, contractclient, contracttype, Address, Bytes, Env, Val, Vec};
 
+/// Lifecycle and authorization hooks a plugin can subscribe to.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PluginHook {
+    Install,
+    Uninstall,
+    Auth,
+    /// Configuration hooks of `ConfigurablePlugin`. Never assumed for plugins that do not
+    /// declare their hooks.
+    Config,
+    /// Authorization hook that also receives the keys of the signers who authorized.
+    /// Never assumed for plugins that do not declare their hooks.
+    SignedAuth,
+}
+
+/// A plugin to install together with its install-time configuration.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PluginInstallParams {
+    pub plugin: Address,
+    /// Opaque configuration passed to `on_install_with_config`, encoded as the plugin
+    /// defines. Must be empty for plugins not subscribed to `PluginHook::Config`.
+    pub config: Bytes,
+}
+
+/// Hooks every plugin implements.
 #[contractclient(name = "SmartAccountPluginClient")]
 pub trait SmartAccountPlugin {
     fn on_install(env: &Env, source: Address);
     fn on_uninstall(env: &Env, source: Address);
     fn on_auth(env: &Env, source: Address, contexts: Vec<Context>);
 }
+
+/// Optional interface through which a plugin declares the hooks it implements.
+///
+/// The smart account queries it once at install time. Plugins that do not implement it
+/// are subscribed to every hook.
+#[contractclient(name = "PluginHooksClient")]
+pub trait PluginHooks {
+    fn hooks(env: &Env) -> Vec<PluginHook>;
+}
+
+/// Optional interface (v2 of the plugin interface) for plugins that take a configuration.
+///
+/// For plugins subscribed to `PluginHook::Config`, the smart account calls
+/// `on_install_with_config` instead of `on_install` with the configuration supplied to
+/// `install_plugin` (or the constructor), so the plugin is never installed unconfigured.
+/// `on_config` is invoked by `update_plugin_config`.
+#[contractclient(name = "ConfigurablePluginClient")]
+pub trait ConfigurablePlugin {
+    fn on_install_with_config(env: &Env, source: Address, config: Bytes);
+    fn on_config(env: &Env, source: Address, config: Bytes);
+}
+
+/// Optional interface for plugins that need to know who authorized a transaction.
+///
+/// Invoked during `__check_auth` for plugins subscribed to `PluginHook::SignedAuth`, after
+/// the signatures have been verified. `signers` holds the smart account's signer keys, or
+/// the address of the executor plugin for calls made through `execute_from_plugin`.
+#[contractclient(name = "SignerAwarePluginClient")]
+pub trait SignerAwarePlugin {
+    fn on_signed_auth(env: &Env, source: Address, contexts: Vec<Context>, signers: Vec<Val>);
+}
@@ -0,0 +1,12 @@
+use soroban_sdk::{contractclient, Address, Env, Val, Vec};
+
+/// Privileged callback through which an account's designated recovery plugin rotates
+/// its admin signers.
+///
+/// `remove` holds the `SignerKey`s of the admins to remove and `add` the admin `Signer`s to
+/// add, as defined by the smart account. They are opaque to the plugin, which only relays
+/// what its guardians approved.
+#[contractclient(name = "RecoverableAccountClient")]
+pub trait RecoverableAccount {
+    fn rotate_admins(env: &Env, plugin: Address, remove: Vec<Val>, add: Vec<Val>);
+}
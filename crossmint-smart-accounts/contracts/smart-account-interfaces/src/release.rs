@@ -0,0 +1,10 @@
+use soroban_sdk::{contractclient, BytesN, Env};
+
+/// Registry of wasm releases an account may upgrade to.
+///
+/// Accounts that restrict upgrades to a registry look the new wasm hash up before
+/// swapping their code. Implemented by `ContractFactory`.
+#[contractclient(name = "ReleaseRegistryClient")]
+pub trait ReleaseRegistry {
+    fn is_published(env: &Env, wasm_hash: BytesN<32>) -> bool;
+}
@@ -1,28 +1,46 @@
+use crate::auth::admins::{sync_cached_admin, uncache_admin, MAX_ADMINS};
 use crate::auth::core::authorizer::Authorizer;
+use crate::auth::index::{indexed_signers, signer_index};
 use crate::auth::permissions::{PolicyCallback, SignerPolicy, SignerRole};
 use crate::auth::proof::SignatureProofs;
 use crate::auth::signer::{Signer, SignerKey};
 use crate::config::{
-    ADMIN_COUNT_KEY, PLUGINS_KEY, TOPIC_PLUGIN, TOPIC_SIGNER, VERB_ADDED, VERB_INSTALLED,
-    VERB_REVOKED, VERB_UNINSTALLED, VERB_UNINSTALL_FAILED, VERB_UPDATED,
+    ADMIN_COUNT_KEY, PLUGINS_KEY, RECOVERY_PLUGIN_KEY, TOPIC_PLUGIN, TOPIC_SIGNER, TOPIC_UPGRADE,
+    UPGRADE_REGISTRY_KEY, UPGRADE_SIGNERS_KEY, VERB_ADDED, VERB_CONFIGURED, VERB_EXECUTED,
+    VERB_INSTALLED, VERB_MODE_CHANGED, VERB_RECOVERED, VERB_RECOVERY_SET, VERB_REGISTRY_SET,
+    VERB_REVOKED, VERB_SCOPE_SET, VERB_UNINSTALLED, VERB_UNINSTALL_FAILED, VERB_UPDATED,
 };
 use crate::error::Error;
 use crate::events::{
-    PluginInstalledEvent, PluginUninstallFailedEvent, PluginUninstalledEvent, SignerAddedEvent,
-    SignerRevokedEvent, SignerUpdatedEvent,
+    AdminsRecoveredEvent, ExecutorScopeSetEvent, PluginConfiguredEvent, PluginExecutedEvent,
+    PluginInstalledEvent, PluginModeChangedEvent, PluginUninstallFailedEvent,
+    PluginUninstalledEvent, RecoveryPluginSetEvent, SignerAddedEvent, SignerRevokedEvent,
+    SignerUpdatedEvent, UpgradeRegistrySetEvent,
+};
+use crate::executor::{
+    authorize_call, call_contexts, executor_scope, extend_executor_ttl, remove_executor_scope,
+    store_executor_scope, ExecutorCall, ExecutorScope,
 };
 use crate::handle_nested_result_failure;
 use crate::interface::SmartAccountInterface;
-use crate::plugin::SmartAccountPluginClient;
+use crate::migration::{
+    account_version, ensure_not_migrating, init_schema_version, is_migration_pending,
+    run_migrations, AccountVersion,
+};
+use crate::plugin::{
+    declared_hooks, installed_plugins, plugin_info, ConfigurablePluginClient, PluginHook,
+    PluginInfo, PluginInstallParams, PluginMode, SmartAccountPluginClient,
+};
 use initializable::{only_not_initialized, Initializable};
+use smart_account_interfaces::ReleaseRegistryClient;
 use soroban_sdk::{
     auth::{Context, CustomAccountInterface},
     contract, contractimpl,
     crypto::Hash,
-    map, panic_with_error, Address, Env, Map, Symbol, Vec,
+    map, panic_with_error, vec, Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, Val, Vec,
 };
-use storage::Storage;
-use upgradeable::{SmartAccountUpgradeable, SmartAccountUpgradeableAuth};
+use storage::{Namespace, Storage, StorageEvents, Store};
+use upgradeable::{SmartAccountUpgradeableAuth, SmartAccountUpgradeableMigratableInternal};
 
 /// SmartAccount is a multi-signature account contract that provides enhanced security
 /// through role-based access control, policy-based authorization, and an extensible plugin system.
@@ -32,14 +50,43 @@ use upgradeable::{SmartAccountUpgradeable, SmartAccountUpgradeableAuth};
 #[contract]
 pub struct SmartAccount;
 
-// Implements SmartAccountUpgradeable trait to allow the contract to be upgraded
-// by authorized signers through the upgrade mechanism
-upgradeable::impl_upgradeable!(SmartAccount);
+// Implements SmartAccountUpgradeableMigratable trait to allow the contract to be upgraded
+// by authorized signers, followed by a migration of the storage layout
+upgradeable::impl_upgradeable!(SmartAccount, migration_data = Bytes);
 
 impl SmartAccountUpgradeableAuth for SmartAccount {
     fn _require_auth_upgrade(e: &Env) {
         e.current_contract_address().require_auth();
     }
+
+    /// The signers of the authorization, as recorded by `__check_auth`
+    fn _upgrade_authorized_by(e: &Env) -> Vec<Val> {
+        SmartAccount::take_upgrade_signers(e)
+            .unwrap_or_else(|| vec![e, e.current_contract_address().into_val(e)])
+    }
+}
+
+impl SmartAccountUpgradeableMigratableInternal for SmartAccount {
+    /// Empty, or the XDR-encoded `Vec<SignerKey>` of the account's signers for migrations
+    /// that need to reach signers stored before the signer index, see `migration`
+    type MigrationData = Bytes;
+
+    fn _migrate(e: &Env, migration_data: &Bytes) {
+        run_migrations(e, migration_data).unwrap_or_else(|err| panic_with_error!(e, err));
+    }
+
+    fn _validate_upgrade(e: &Env, new_wasm_hash: &BytesN<32>) {
+        let Some(registry) = SmartAccount::upgrade_registry(e) else {
+            return;
+        };
+        let published = matches!(
+            ReleaseRegistryClient::new(e, &registry).try_is_published(new_wasm_hash),
+            Ok(Ok(true))
+        );
+        if !published {
+            panic_with_error!(e, Error::UpgradeNotPublished);
+        }
+    }
 }
 
 // Implements Initializable trait to allow the contract to be initialized.
@@ -49,10 +96,15 @@ impl Initializable for SmartAccount {}
 
 impl SmartAccount {
     /// Only requires authorization if the contract is already initialized.
-    pub fn require_auth_if_initialized(env: &Env) {
+    ///
+    /// Fails with `MigrationPending` while a migration is pending, since administrative
+    /// operations must not run against storage in an outdated schema.
+    pub fn require_auth_if_initialized(env: &Env) -> Result<(), Error> {
+        ensure_not_migrating(env)?;
         if Self::is_initialized(env) {
             env.current_contract_address().require_auth();
         }
+        Ok(())
     }
 }
 
@@ -64,7 +116,7 @@ impl SmartAccount {
 /// for all administrative operations on the smart account.
 #[contractimpl]
 impl SmartAccountInterface for SmartAccount {
-    fn __constructor(env: Env, signers: Vec<Signer>, plugins: Vec<Address>) {
+    fn __constructor(env: Env, signers: Vec<Signer>, plugins: Vec<PluginInstallParams>) {
         only_not_initialized!(&env);
 
         // Check that there is at least one admin signer to prevent the contract from being locked out.
@@ -73,7 +125,7 @@ impl SmartAccountInterface for SmartAccount {
         }
 
         // Initialize admin count to 0 before adding signers
-        Storage::persistent()
+        Storage::instance()
             .store(&env, &ADMIN_COUNT_KEY, &0u32)
             .unwrap_or_else(|e| panic_with_error!(env, Error::from(e)));
 
@@ -85,41 +137,27 @@ impl SmartAccountInterface for SmartAccount {
         // Initialize plugins storage
         let storage = Storage::instance();
         storage
-            .store::<Symbol, Map<Address, ()>>(&env, &PLUGINS_KEY, &map![&env])
+            .store::<Symbol, Map<Address, PluginInfo>>(&env, &PLUGINS_KEY, &map![&env])
             .unwrap();
         // Install plugins
-        for plugin in plugins {
-            SmartAccount::install_plugin(&env, plugin)
+        for PluginInstallParams { plugin, config } in plugins {
+            SmartAccount::install_plugin(&env, plugin, config)
                 .unwrap_or_else(|e| panic_with_error!(env, e));
         }
 
+        init_schema_version(&env).unwrap_or_else(|e| panic_with_error!(env, e));
+
         // Initialize the contract
         SmartAccount::initialize(&env).unwrap_or_else(|e| panic_with_error!(env, e));
     }
 
     fn add_signer(env: &Env, signer: Signer) -> Result<(), Error> {
-        Self::require_auth_if_initialized(env);
-        let key = signer.clone().into();
-        let storage = Storage::persistent();
-        storage.store::<SignerKey, Signer>(env, &key, &signer)?;
-
-        // Handle role-specific initialization
-        match signer.role() {
-            SignerRole::Standard(policies) => {
-                Self::activate_policies(env, &policies)?;
-            }
-            SignerRole::Admin => {
-                Self::increment_admin_count(env)?;
-            }
-        }
-        env.events()
-            .publish((TOPIC_SIGNER, VERB_ADDED), SignerAddedEvent::from(signer));
-
-        Ok(())
+        Self::require_auth_if_initialized(env)?;
+        Self::store_signer(env, signer)
     }
 
     fn update_signer(env: &Env, signer: Signer) -> Result<(), Error> {
-        Self::require_auth_if_initialized(env);
+        Self::require_auth_if_initialized(env)?;
         let key = signer.clone().into();
         let storage = Storage::persistent();
         let old_signer = storage
@@ -127,10 +165,11 @@ impl SmartAccountInterface for SmartAccount {
             .ok_or(Error::SignerNotFound)?;
 
         // Handle role transitions: admin count and policy lifecycle callbacks
-        Self::handle_role_transition(env, &old_signer.role(), &signer.role())?;
+        Self::handle_role_transition(env, &key, &old_signer.role(), &signer.role())?;
 
         // Update the signer in storage
         storage.update::<SignerKey, Signer>(env, &key, &signer)?;
+        sync_cached_admin(env, &key, &signer)?;
         env.events().publish(
             (TOPIC_SIGNER, VERB_UPDATED),
             SignerUpdatedEvent::from(signer),
@@ -140,7 +179,7 @@ impl SmartAccountInterface for SmartAccount {
     }
 
     fn revoke_signer(env: &Env, signer_key: SignerKey) -> Result<(), Error> {
-        Self::require_auth_if_initialized(env);
+        Self::require_auth_if_initialized(env)?;
 
         let storage = Storage::persistent();
 
@@ -152,10 +191,10 @@ impl SmartAccountInterface for SmartAccount {
             return Err(Error::CannotRevokeAdminSigner);
         }
 
-        storage.delete::<SignerKey>(env, &signer_key)?;
+        Self::delete_signer(env, &signer_key)?;
         // Deactivate policies if this is a Standard signer
         if let SignerRole::Standard(policies) = signer_to_revoke.role() {
-            Self::deactivate_policies(env, &policies)?;
+            Self::deactivate_policies(env, &signer_key, &policies)?;
         }
         env.events().publish(
             (TOPIC_SIGNER, VERB_REVOKED),
@@ -174,60 +213,99 @@ impl SmartAccountInterface for SmartAccount {
         Ok(Storage::persistent().has::<SignerKey>(env, &signer_key))
     }
 
-    fn install_plugin(env: &Env, plugin: Address) -> Result<(), Error> {
-        Self::require_auth_if_initialized(env);
+    fn install_plugin(env: &Env, plugin: Address, config: Bytes) -> Result<(), Error> {
+        Self::require_auth_if_initialized(env)?;
 
-        // Store the plugin in the storage
-        let storage = Storage::instance();
-        let mut existing_plugins = storage
-            .get::<Symbol, Map<Address, ()>>(env, &PLUGINS_KEY)
-            .unwrap();
+        let mut existing_plugins = installed_plugins(env);
         if existing_plugins.contains_key(plugin.clone()) {
             return Err(Error::PluginAlreadyInstalled);
         }
-        existing_plugins.set(plugin.clone(), ());
-        storage.update::<Symbol, Map<Address, ()>>(env, &PLUGINS_KEY, &existing_plugins)?;
 
-        // Call the plugin's on_install callback for initialization
-        SmartAccountPluginClient::new(env, &plugin)
-            .try_on_install(&env.current_contract_address())
-            .map_err(|_| Error::PluginInitializationFailed)?
-            .map_err(|_| Error::PluginInitializationFailed)?;
+        // Record the hooks the plugin subscribes to, so that only subscribers are invoked
+        let hooks = declared_hooks(env, &plugin);
+        let info = PluginInfo::new(env, hooks.clone());
+        // A config must not be silently dropped by a plugin that cannot receive it
+        if !config.is_empty() && !info.is_subscribed(PluginHook::Config) {
+            return Err(Error::PluginHookNotSubscribed);
+        }
+        existing_plugins.set(plugin.clone(), info.clone());
+        Self::save_plugins(env, &existing_plugins)?;
+
+        // Configurable plugins are initialized with their config, others through the
+        // original on_install callback
+        if info.is_subscribed(PluginHook::Config) {
+            ConfigurablePluginClient::new(env, &plugin)
+                .try_on_install_with_config(&env.current_contract_address(), &config)
+                .map_err(|_| Error::PluginInitializationFailed)?
+                .map_err(|_| Error::PluginInitializationFailed)?;
+        } else if info.is_subscribed(PluginHook::Install) {
+            SmartAccountPluginClient::new(env, &plugin)
+                .try_on_install(&env.current_contract_address())
+                .map_err(|_| Error::PluginInitializationFailed)?
+                .map_err(|_| Error::PluginInitializationFailed)?;
+        }
 
         env.events().publish(
             (TOPIC_PLUGIN, VERB_INSTALLED),
-            PluginInstalledEvent { plugin },
+            PluginInstalledEvent { plugin, hooks },
         );
 
         Ok(())
     }
 
-    fn uninstall_plugin(env: &Env, plugin: Address) -> Result<(), Error> {
-        Self::require_auth_if_initialized(env);
-
-        let storage = Storage::instance();
-        let mut existing_plugins = storage
-            .get::<Symbol, Map<Address, ()>>(env, &PLUGINS_KEY)
-            .unwrap();
+    fn update_plugin_config(env: &Env, plugin: Address, config: Bytes) -> Result<(), Error> {
+        Self::require_auth_if_initialized(env)?;
 
-        if !existing_plugins.contains_key(plugin.clone()) {
-            return Err(Error::PluginNotFound);
+        if !plugin_info(env, &plugin)?.is_subscribed(PluginHook::Config) {
+            return Err(Error::PluginHookNotSubscribed);
         }
-        existing_plugins.remove(plugin.clone());
-        storage.update(env, &PLUGINS_KEY, &existing_plugins)?;
+
+        ConfigurablePluginClient::new(env, &plugin)
+            .try_on_config(&env.current_contract_address(), &config)
+            .map_err(|_| Error::PluginConfigUpdateFailed)?
+            .map_err(|_| Error::PluginConfigUpdateFailed)?;
+
+        env.events().publish(
+            (TOPIC_PLUGIN, VERB_CONFIGURED),
+            PluginConfiguredEvent { plugin },
+        );
+
+        Ok(())
+    }
+
+    fn uninstall_plugin(env: &Env, plugin: Address) -> Result<(), Error> {
+        Self::require_auth_if_initialized(env)?;
+
+        let wants_uninstall_hook = Self::remove_plugin(env, &plugin)?;
 
         // Counterwise to install, we don't want to fail if the plugin's on_uninstall fails,
         // as it would prevent an admin from uninstalling a potentially-malicious plugin.
-        let res = SmartAccountPluginClient::new(env, &plugin)
-            .try_on_uninstall(&env.current_contract_address());
-        handle_nested_result_failure!(res, {
-            env.events().publish(
-                (TOPIC_PLUGIN, VERB_UNINSTALL_FAILED),
-                PluginUninstallFailedEvent {
-                    plugin: plugin.clone(),
-                },
-            );
-        });
+        if wants_uninstall_hook {
+            let res = SmartAccountPluginClient::new(env, &plugin)
+                .try_on_uninstall(&env.current_contract_address());
+            handle_nested_result_failure!(res, {
+                env.events().publish(
+                    (TOPIC_PLUGIN, VERB_UNINSTALL_FAILED),
+                    PluginUninstallFailedEvent {
+                        plugin: plugin.clone(),
+                    },
+                );
+            });
+        }
+
+        env.events().publish(
+            (TOPIC_PLUGIN, VERB_UNINSTALLED),
+            PluginUninstalledEvent { plugin },
+        );
+
+        Ok(())
+    }
+
+    fn emergency_uninstall_plugin(env: &Env, plugin: Address) -> Result<(), Error> {
+        // Authorizing this call skips plugin on_auth hooks, see `Authorizer::skips_plugin_hooks`
+        Self::require_auth_if_initialized(env)?;
+
+        Self::remove_plugin(env, &plugin)?;
 
         env.events().publish(
             (TOPIC_PLUGIN, VERB_UNINSTALLED),
@@ -237,11 +315,210 @@ impl SmartAccountInterface for SmartAccount {
         Ok(())
     }
 
+    fn set_plugin_mode(env: &Env, plugin: Address, mode: PluginMode) -> Result<(), Error> {
+        Self::require_auth_if_initialized(env)?;
+
+        let mut existing_plugins = installed_plugins(env);
+        let mut info = existing_plugins
+            .get(plugin.clone())
+            .ok_or(Error::PluginNotFound)?;
+        info.mode = mode;
+        existing_plugins.set(plugin.clone(), info);
+        Self::save_plugins(env, &existing_plugins)?;
+
+        env.events().publish(
+            (TOPIC_PLUGIN, VERB_MODE_CHANGED),
+            PluginModeChangedEvent { plugin, mode },
+        );
+
+        Ok(())
+    }
+
+    fn set_executor_scope(env: &Env, plugin: Address, scope: ExecutorScope) -> Result<(), Error> {
+        Self::require_auth_if_initialized(env)?;
+
+        plugin_info(env, &plugin)?;
+        scope.validate()?;
+        store_executor_scope(env, &plugin, &scope)?;
+
+        env.events().publish(
+            (TOPIC_PLUGIN, VERB_SCOPE_SET),
+            ExecutorScopeSetEvent { plugin, scope },
+        );
+
+        Ok(())
+    }
+
+    fn execute_from_plugin(
+        env: &Env,
+        plugin: Address,
+        calls: Vec<ExecutorCall>,
+    ) -> Result<Vec<Val>, Error> {
+        ensure_not_migrating(env)?;
+        plugin.require_auth();
+
+        plugin_info(env, &plugin)?;
+        let scope = executor_scope(env, &plugin)?;
+
+        // Authorize every call before executing any, so a batch never runs partially
+        for call in calls.iter() {
+            authorize_call(env, &plugin, &scope, &call)?;
+        }
+        // The calls bypass `__check_auth`, so the other plugins see them here instead
+        Authorizer::call_plugins_on_execute(env, &plugin, &call_contexts(env, &calls))?;
+
+        // The account is the direct invoker, so its own require_auth calls are satisfied
+        let mut results = Vec::new(env);
+        for call in calls.iter() {
+            results.push_back(env.invoke_contract::<Val>(&call.contract, &call.fn_name, call.args));
+        }
+
+        env.events().publish(
+            (TOPIC_PLUGIN, VERB_EXECUTED),
+            PluginExecutedEvent { plugin, calls },
+        );
+
+        Ok(results)
+    }
+
+    fn set_recovery_plugin(env: &Env, plugin: Option<Address>) -> Result<(), Error> {
+        Self::require_auth_if_initialized(env)?;
+
+        let store = Self::recovery_plugin_store();
+        match &plugin {
+            Some(plugin) => {
+                plugin_info(env, plugin)?;
+                store.set(env, &RECOVERY_PLUGIN_KEY, plugin)?;
+            }
+            None => {
+                if store.has(env, &RECOVERY_PLUGIN_KEY) {
+                    store.delete(env, &RECOVERY_PLUGIN_KEY)?;
+                }
+            }
+        }
+
+        env.events().publish(
+            (TOPIC_PLUGIN, VERB_RECOVERY_SET),
+            RecoveryPluginSetEvent { plugin },
+        );
+
+        Ok(())
+    }
+
+    fn rotate_admins(
+        env: &Env,
+        plugin: Address,
+        remove: Vec<SignerKey>,
+        add: Vec<Signer>,
+    ) -> Result<(), Error> {
+        ensure_not_migrating(env)?;
+        plugin.require_auth();
+
+        let recovery_plugin = Self::recovery_plugin_store().get(env, &RECOVERY_PLUGIN_KEY);
+        if recovery_plugin != Some(plugin.clone()) {
+            return Err(Error::NotRecoveryPlugin);
+        }
+
+        // Remove the old admins first, so that an account at `MAX_ADMINS` can still replace
+        // them. The count may drop to zero in between, so it is checked once the new admins
+        // are added.
+        let storage = Storage::persistent();
+        for signer_key in remove.iter() {
+            let signer = storage
+                .get::<SignerKey, Signer>(env, &signer_key)
+                .ok_or(Error::SignerNotFound)?;
+            if signer.role() != SignerRole::Admin {
+                return Err(Error::InvalidRecoverySigner);
+            }
+            Self::delete_signer(env, &signer_key)?;
+            env.events().publish(
+                (TOPIC_SIGNER, VERB_REVOKED),
+                SignerRevokedEvent::from(signer),
+            );
+        }
+        let remaining = Self::admin_count(env)
+            .checked_sub(remove.len())
+            .ok_or(Error::CannotDowngradeLastAdmin)?;
+        Storage::instance().update::<Symbol, u32>(env, &ADMIN_COUNT_KEY, &remaining)?;
+
+        let mut added = Vec::new(env);
+        for signer in add.iter() {
+            if signer.role() != SignerRole::Admin {
+                return Err(Error::InvalidRecoverySigner);
+            }
+            added.push_back(signer.clone().into());
+            Self::store_signer(env, signer)?;
+        }
+        if Self::admin_count(env) == 0 {
+            return Err(Error::CannotDowngradeLastAdmin);
+        }
+
+        env.events().publish(
+            (TOPIC_PLUGIN, VERB_RECOVERED),
+            AdminsRecoveredEvent {
+                plugin,
+                removed: remove,
+                added,
+            },
+        );
+
+        Ok(())
+    }
+
     fn is_plugin_installed(env: &Env, plugin: Address) -> bool {
-        Storage::instance()
-            .get::<Symbol, Map<Address, ()>>(env, &PLUGINS_KEY)
-            .unwrap()
-            .contains_key(plugin)
+        installed_plugins(env).contains_key(plugin)
+    }
+
+    fn list_plugins(env: &Env) -> Map<Address, PluginInfo> {
+        installed_plugins(env)
+    }
+
+    fn set_upgrade_registry(env: &Env, registry: Address) -> Result<(), Error> {
+        Self::require_auth_if_initialized(env)?;
+
+        // Otherwise a compromised admin could lift the restriction before upgrading
+        let storage = Storage::instance();
+        if storage.has::<Symbol>(env, &UPGRADE_REGISTRY_KEY) {
+            return Err(Error::UpgradeRegistryLocked);
+        }
+        storage.store::<Symbol, Address>(env, &UPGRADE_REGISTRY_KEY, &registry)?;
+
+        env.events().publish(
+            (TOPIC_UPGRADE, VERB_REGISTRY_SET),
+            UpgradeRegistrySetEvent { registry },
+        );
+
+        Ok(())
+    }
+
+    fn upgrade_registry(env: &Env) -> Option<Address> {
+        Storage::instance().get::<Symbol, Address>(env, &UPGRADE_REGISTRY_KEY)
+    }
+
+    fn version(env: &Env) -> AccountVersion {
+        account_version(env)
+    }
+
+    fn extend_ttl(env: &Env) -> Result<(), Error> {
+        // Extends the instance, holding the plugin registry, and the contract code
+        Storage::instance().extend_ttl::<Symbol>(env, &PLUGINS_KEY)?;
+
+        let storage = Storage::persistent();
+        let index = signer_index();
+        index.extend_ttl(env, 0, index.len(env))?;
+        for signer_key in indexed_signers(env).iter() {
+            storage.extend_ttl::<SignerKey>(env, &signer_key)?;
+        }
+        for plugin in installed_plugins(env).keys().iter() {
+            extend_executor_ttl(env, &plugin)?;
+        }
+        Ok(())
+    }
+
+    fn set_storage_events(env: &Env, mode: StorageEvents) -> Result<(), Error> {
+        Self::require_auth_if_initialized(env)?;
+        storage::set_storage_events(env, mode);
+        Ok(())
     }
 }
 
@@ -250,9 +527,94 @@ impl SmartAccountInterface for SmartAccount {
 // ============================================================================
 
 impl SmartAccount {
+    /// Plugin allowed to rotate the admins, under `RECOVERY_PLUGIN_KEY`
+    fn recovery_plugin_store() -> Store<Symbol, Address> {
+        Store::new(Storage::instance(), Namespace::Recovery)
+    }
+
+    /// Signers of the upgrade in progress, under `UPGRADE_SIGNERS_KEY`
+    fn upgrade_signers_store() -> Store<Symbol, Vec<Val>> {
+        Store::new(Storage::instance(), Namespace::Upgrade)
+    }
+
+    /// Remembers who signed an upgrade until `upgrade` records it in the history
+    fn record_upgrade_signers(env: &Env, signers: &Vec<Val>) -> Result<(), Error> {
+        Self::upgrade_signers_store().set(env, &UPGRADE_SIGNERS_KEY, signers)?;
+        Ok(())
+    }
+
+    fn take_upgrade_signers(env: &Env) -> Option<Vec<Val>> {
+        let store = Self::upgrade_signers_store();
+        let signers = store.get(env, &UPGRADE_SIGNERS_KEY)?;
+        let _ = store.delete(env, &UPGRADE_SIGNERS_KEY);
+        Some(signers)
+    }
+
+    /// Stores a new signer and runs its role-specific initialization
+    fn store_signer(env: &Env, signer: Signer) -> Result<(), Error> {
+        let key: SignerKey = signer.clone().into();
+        let storage = Storage::persistent();
+        storage.store::<SignerKey, Signer>(env, &key, &signer)?;
+        sync_cached_admin(env, &key, &signer)?;
+        signer_index().insert(env, &key)?;
+
+        // Handle role-specific initialization
+        match signer.role() {
+            SignerRole::Standard(policies) => {
+                Self::activate_policies(env, &policies)?;
+            }
+            SignerRole::Admin => {
+                Self::increment_admin_count(env)?;
+            }
+        }
+        env.events()
+            .publish((TOPIC_SIGNER, VERB_ADDED), SignerAddedEvent::from(signer));
+
+        Ok(())
+    }
+
+    /// Removes a signer from storage and from the signer index
+    fn delete_signer(env: &Env, signer_key: &SignerKey) -> Result<(), Error> {
+        Storage::persistent().delete::<SignerKey>(env, signer_key)?;
+        uncache_admin(env, signer_key)?;
+        signer_index().remove(env, signer_key)?;
+        Ok(())
+    }
+
+    /// Writes the plugin registry back to instance storage
+    fn save_plugins(env: &Env, plugins: &Map<Address, PluginInfo>) -> Result<(), Error> {
+        Storage::instance().update::<Symbol, Map<Address, PluginInfo>>(
+            env,
+            &PLUGINS_KEY,
+            plugins,
+        )?;
+        Ok(())
+    }
+
+    /// Removes a plugin from the registry.
+    ///
+    /// Returns whether the plugin is subscribed to the uninstall hook.
+    fn remove_plugin(env: &Env, plugin: &Address) -> Result<bool, Error> {
+        let mut existing_plugins = installed_plugins(env);
+        let info = existing_plugins
+            .get(plugin.clone())
+            .ok_or(Error::PluginNotFound)?;
+        existing_plugins.remove(plugin.clone());
+        Self::save_plugins(env, &existing_plugins)?;
+        remove_executor_scope(env, plugin)?;
+
+        let recovery = Self::recovery_plugin_store();
+        if recovery.get(env, &RECOVERY_PLUGIN_KEY) == Some(plugin.clone()) {
+            recovery.delete(env, &RECOVERY_PLUGIN_KEY)?;
+        }
+
+        Ok(info.is_subscribed(PluginHook::Uninstall))
+    }
+
     /// Handles role transitions including admin count management and policy lifecycle callbacks
     fn handle_role_transition(
         env: &Env,
+        signer_key: &SignerKey,
         old_role: &SignerRole,
         new_role: &SignerRole,
     ) -> Result<(), Error> {
@@ -265,11 +627,11 @@ impl SmartAccount {
             // Standard → Admin: increase admin count, deactivate policies
             (SignerRole::Standard(policies), SignerRole::Admin) => {
                 Self::increment_admin_count(env)?;
-                Self::deactivate_policies(env, policies)?;
+                Self::deactivate_policies(env, signer_key, policies)?;
             }
             // Standard → Standard: handle policy set changes
             (SignerRole::Standard(old_policies), SignerRole::Standard(new_policies)) => {
-                Self::handle_policy_set_changes(env, old_policies, new_policies)?;
+                Self::handle_policy_set_changes(env, signer_key, old_policies, new_policies)?;
             }
             // Admin → Admin: no changes needed
             (SignerRole::Admin, SignerRole::Admin) => {}
@@ -277,12 +639,15 @@ impl SmartAccount {
         Ok(())
     }
 
+    fn admin_count(env: &Env) -> u32 {
+        Storage::instance()
+            .get::<Symbol, u32>(env, &ADMIN_COUNT_KEY)
+            .unwrap_or(0)
+    }
+
     /// Decrements admin count with validation
     fn decrement_admin_count(env: &Env) -> Result<(), Error> {
-        let storage = Storage::persistent();
-        let count = storage
-            .get::<Symbol, u32>(env, &ADMIN_COUNT_KEY)
-            .unwrap_or(0);
+        let count = Self::admin_count(env);
 
         if count <= 1 {
             return Err(Error::CannotDowngradeLastAdmin);
@@ -291,18 +656,17 @@ impl SmartAccount {
         let new_count = count
             .checked_sub(1)
             .ok_or(Error::CannotDowngradeLastAdmin)?;
-        storage.update::<Symbol, u32>(env, &ADMIN_COUNT_KEY, &new_count)?;
+        Storage::instance().update::<Symbol, u32>(env, &ADMIN_COUNT_KEY, &new_count)?;
         Ok(())
     }
 
-    /// Increments admin count with validation
+    /// Increments admin count, up to `MAX_ADMINS`
     fn increment_admin_count(env: &Env) -> Result<(), Error> {
-        let storage = Storage::persistent();
-        let count = storage
-            .get::<Symbol, u32>(env, &ADMIN_COUNT_KEY)
-            .unwrap_or(0);
-        let new_count = count.checked_add(1).ok_or(Error::MaxSignersReached)?;
-        storage.update::<Symbol, u32>(env, &ADMIN_COUNT_KEY, &new_count)?;
+        let count = Self::admin_count(env);
+        if count >= MAX_ADMINS {
+            return Err(Error::MaxAdminsReached);
+        }
+        Storage::instance().update::<Symbol, u32>(env, &ADMIN_COUNT_KEY, &(count + 1))?;
         Ok(())
     }
 
@@ -315,9 +679,13 @@ impl SmartAccount {
     }
 
     /// Deactivates policies by calling their on_revoke callbacks
-    fn deactivate_policies(env: &Env, policies: &Vec<SignerPolicy>) -> Result<(), Error> {
+    fn deactivate_policies(
+        env: &Env,
+        signer_key: &SignerKey,
+        policies: &Vec<SignerPolicy>,
+    ) -> Result<(), Error> {
         for policy in policies {
-            policy.on_revoke(env)?;
+            policy.on_revoke(env, signer_key)?;
         }
         Ok(())
     }
@@ -329,6 +697,7 @@ impl SmartAccount {
     /// - Policies in both sets: no callbacks (unchanged)
     fn handle_policy_set_changes(
         env: &Env,
+        signer_key: &SignerKey,
         old_policies: &Vec<SignerPolicy>,
         new_policies: &Vec<SignerPolicy>,
     ) -> Result<(), Error> {
@@ -348,7 +717,7 @@ impl SmartAccount {
         if new_policies.is_empty() {
             // All old policies need to be revoked
             for policy in old_policies {
-                policy.on_revoke(env)?;
+                policy.on_revoke(env, signer_key)?;
             }
             return Ok(());
         }
@@ -367,7 +736,7 @@ impl SmartAccount {
                 new_policy_set.set(old_policy, false);
             } else {
                 // Policy only in old set, revoke it
-                old_policy.on_revoke(env)?;
+                old_policy.on_revoke(env, signer_key)?;
             }
         }
 
@@ -438,8 +807,17 @@ impl CustomAccountInterface for SmartAccount {
         auth_payloads: SignatureProofs,
         auth_contexts: Vec<Context>,
     ) -> Result<(), Error> {
+        if is_migration_pending(&env) && !Authorizer::is_upgrade_auth(&env, &auth_contexts) {
+            return Err(Error::MigrationPending);
+        }
         Authorizer::check(&env, signature_payload, &auth_payloads, &auth_contexts)?;
-        Authorizer::call_plugins_on_auth(&env, &auth_contexts)?;
+        if !Authorizer::skips_plugin_hooks(&env, &auth_contexts) {
+            Authorizer::call_plugins_on_auth(&env, &auth_payloads, &auth_contexts)?;
+        }
+        if Authorizer::authorizes_upgrade(&env, &auth_contexts) {
+            let signers = Authorizer::signer_keys(&env, &auth_payloads);
+            SmartAccount::record_upgrade_signers(&env, &signers)?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,71 @@
+//! Copy of the admin signers in instance storage.
+//!
+//! Instance storage is loaded with the contract, so `Authorizer::check` can find admin
+//! signers without reading their persistent entries. Persistent entries stay the source of
+//! truth: every write of an admin signer is mirrored here. Admins added before the cache
+//! existed are cached by the schema 5 migration; until then they are read from their
+//! persistent entries.
+//!
+//! Instance storage is loaded on every call and counts towards the ledger entry size limit,
+//! so an account may have at most `MAX_ADMINS` admins. Accounts that had more before the
+//! cap keep them, but cannot add admins until they are back under it.
+use soroban_sdk::{Env, Map, Symbol, Vec};
+use storage::{Namespace, Storage, Store};
+
+use crate::auth::permissions::SignerRole;
+use crate::auth::signer::{Signer, SignerKey};
+use crate::config::ADMIN_SIGNERS_KEY;
+use crate::error::Error;
+
+/// Most admin signers an account may have, so that the cache stays small.
+pub const MAX_ADMINS: u32 = 10;
+
+fn store() -> Store<Symbol, Map<SignerKey, Signer>> {
+    Store::new(Storage::instance(), Namespace::Signers)
+}
+
+pub fn cached_admins(env: &Env) -> Map<SignerKey, Signer> {
+    store()
+        .get(env, &ADMIN_SIGNERS_KEY)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+/// Mirrors a signer write: caches the signer if it is an admin, and drops any cached copy
+/// otherwise.
+pub fn sync_cached_admin(env: &Env, key: &SignerKey, signer: &Signer) -> Result<(), Error> {
+    if signer.role() == SignerRole::Admin {
+        let mut admins = cached_admins(env);
+        admins.set(key.clone(), signer.clone());
+        save(env, &admins)
+    } else {
+        uncache_admin(env, key)
+    }
+}
+
+/// Drops the cached copy of a signer, if any.
+pub fn uncache_admin(env: &Env, key: &SignerKey) -> Result<(), Error> {
+    let mut admins = cached_admins(env);
+    if admins.contains_key(key.clone()) {
+        admins.remove(key.clone());
+        save(env, &admins)?;
+    }
+    Ok(())
+}
+
+/// Caches every admin among the stored signers of `signer_keys`, and drops cached copies
+/// of the others. Running it again leaves storage unchanged.
+pub fn cache_admins(env: &Env, signer_keys: &Vec<SignerKey>) -> Result<(), Error> {
+    let persistent = Storage::persistent();
+    for key in signer_keys.iter() {
+        match persistent.get::<SignerKey, Signer>(env, &key) {
+            Some(signer) => sync_cached_admin(env, &key, &signer)?,
+            None => uncache_admin(env, &key)?,
+        }
+    }
+    Ok(())
+}
+
+fn save(env: &Env, admins: &Map<SignerKey, Signer>) -> Result<(), Error> {
+    store().set(env, &ADMIN_SIGNERS_KEY, admins)?;
+    Ok(())
+}
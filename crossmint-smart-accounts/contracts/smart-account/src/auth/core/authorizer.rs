@@ -1,15 +1,22 @@
 /// Authorization service that verifies proofs and enforces role/policy checks.
-use crate::auth::permissions::{AuthorizationCheck, SignerRole};
+use crate::auth::admins::cached_admins;
+use crate::auth::permissions::SignerRole;
 use crate::auth::proof::SignatureProofs;
 use crate::auth::signer::{Signer, SignerKey};
 use crate::auth::signers::SignatureVerifier as _;
-use crate::config::{PLUGINS_KEY, TOPIC_PLUGIN, VERB_AUTH_FAILED};
+use crate::config::{TOPIC_PLUGIN, VERB_AUTH_FAILED};
 use crate::error::Error;
 use crate::events::PluginAuthFailedEvent;
 use crate::handle_nested_result_failure;
-use smart_account_interfaces::SmartAccountPluginClient;
-use soroban_sdk::{auth::Context, crypto::Hash, Env, Vec};
-use soroban_sdk::{Address, Map, String, Symbol};
+use crate::plugin::{
+    installed_plugins, PluginHook, PluginMode, SignerAwarePluginClient, SmartAccountPluginClient,
+};
+use soroban_sdk::{
+    auth::{Context, ContractContext},
+    crypto::Hash,
+    vec, Env, IntoVal, Val, Vec,
+};
+use soroban_sdk::{Address, String, Symbol};
 use storage::Storage;
 
 pub struct Authorizer;
@@ -30,11 +37,16 @@ impl Authorizer {
 
         let mut admin_signers = Vec::new(env);
         let mut standard_signers = Vec::new(env);
+        // Admins are found in instance storage, so they cost no persistent reads
+        let cached_admins = cached_admins(env);
 
         for (signer_key, proof) in proof_map.iter() {
-            let signer = storage
-                .get::<SignerKey, Signer>(env, &signer_key)
-                .ok_or(Error::SignerNotFound)?;
+            let signer = match cached_admins.get(signer_key.clone()) {
+                Some(signer) => signer,
+                None => storage
+                    .get::<SignerKey, Signer>(env, &signer_key)
+                    .ok_or(Error::SignerNotFound)?,
+            };
             signer.verify(env, &signature_payload.to_bytes(), &proof)?;
 
             match signer.role() {
@@ -51,6 +63,7 @@ impl Authorizer {
 
         for signer in standard_signers.iter() {
             if signer.is_authorized(env, auth_contexts) {
+                signer.on_authorized(env, auth_contexts)?;
                 return Ok(());
             }
         }
@@ -58,25 +71,145 @@ impl Authorizer {
         Err(Error::InsufficientPermissions)
     }
 
-    pub fn call_plugins_on_auth(env: &Env, auth_contexts: &Vec<Context>) -> Result<(), Error> {
-        let storage = Storage::instance();
-        for (plugin, _) in storage
-            .get::<Symbol, Map<Address, ()>>(env, &PLUGINS_KEY)
-            .unwrap()
-            .iter()
-        {
-            let res = SmartAccountPluginClient::new(env, &plugin)
-                .try_on_auth(&env.current_contract_address(), auth_contexts);
-            handle_nested_result_failure!(res, {
-                env.events().publish(
-                    (TOPIC_PLUGIN, &plugin, VERB_AUTH_FAILED),
-                    PluginAuthFailedEvent {
-                        plugin: plugin.clone(),
-                        error: String::from_str(env, "Plugin on_auth failed"),
-                    },
+    /// Returns true when every context is an emergency plugin removal on this account.
+    ///
+    /// Such authorizations skip plugin hooks so that a plugin whose `on_auth` always fails
+    /// cannot prevent its own removal. They still require an admin signer, since they
+    /// target the account itself.
+    pub fn skips_plugin_hooks(env: &Env, auth_contexts: &Vec<Context>) -> bool {
+        let emergency_uninstall = Symbol::new(env, "emergency_uninstall_plugin");
+        Self::only_calls_account(env, auth_contexts, |fn_name| fn_name == emergency_uninstall)
+    }
+
+    /// Returns true when every context is a migration or upgrade call on this account.
+    ///
+    /// These are the only authorizations granted while a migration is pending, so that the
+    /// migration can complete, or a faulty upgrade can be replaced.
+    pub fn is_upgrade_auth(env: &Env, auth_contexts: &Vec<Context>) -> bool {
+        let allowed = [
+            Symbol::new(env, "migrate"),
+            Symbol::new(env, "upgrade"),
+            Symbol::new(env, "propose_upgrade"),
+            Symbol::new(env, "cancel_upgrade"),
+            Symbol::new(env, "execute_upgrade"),
+        ];
+        Self::only_calls_account(env, auth_contexts, |fn_name| allowed.contains(&fn_name))
+    }
+
+    /// Returns true when any context is a call on this account that replaces its wasm.
+    pub fn authorizes_upgrade(env: &Env, auth_contexts: &Vec<Context>) -> bool {
+        let upgrade = Symbol::new(env, "upgrade");
+        let execute_upgrade = Symbol::new(env, "execute_upgrade");
+        auth_contexts.iter().any(|context| match context {
+            Context::Contract(ContractContext {
+                contract, fn_name, ..
+            }) => {
+                contract == env.current_contract_address()
+                    && (fn_name == upgrade || fn_name == execute_upgrade)
+            }
+            _ => false,
+        })
+    }
+
+    /// Returns the keys of the signers in the auth entry.
+    pub fn signer_keys(env: &Env, auth_payloads: &SignatureProofs) -> Vec<Val> {
+        let SignatureProofs(proof_map) = auth_payloads;
+        let mut keys = Vec::new(env);
+        for key in proof_map.keys().iter() {
+            keys.push_back(key.into_val(env));
+        }
+        keys
+    }
+
+    fn only_calls_account(
+        env: &Env,
+        auth_contexts: &Vec<Context>,
+        allowed: impl Fn(Symbol) -> bool,
+    ) -> bool {
+        !auth_contexts.is_empty()
+            && auth_contexts.iter().all(|context| match context {
+                Context::Contract(ContractContext {
+                    contract, fn_name, ..
+                }) => contract == env.current_contract_address() && allowed(fn_name),
+                _ => false,
+            })
+    }
+
+    /// Invokes the auth hooks of the installed plugins: `on_auth` for plugins subscribed to
+    /// `Auth`, and `on_signed_auth` with the keys of the signers in the auth entry for plugins
+    /// subscribed to `SignedAuth`.
+    ///
+    /// Failures of enforcing plugins veto the authorization, while failures of advisory
+    /// plugins are only reported through a `PluginAuthFailedEvent`.
+    pub fn call_plugins_on_auth(
+        env: &Env,
+        auth_payloads: &SignatureProofs,
+        auth_contexts: &Vec<Context>,
+    ) -> Result<(), Error> {
+        Self::call_plugin_hooks(env, auth_contexts, None, || {
+            Self::signer_keys(env, auth_payloads)
+        })
+    }
+
+    /// Invokes the auth hooks of the installed plugins for calls an executor plugin makes
+    /// through `execute_from_plugin`, which bypasses `__check_auth`.
+    ///
+    /// `on_signed_auth` receives the executor's address in place of signer keys. The
+    /// executor itself is skipped, since Soroban does not allow calling back into it.
+    pub fn call_plugins_on_execute(
+        env: &Env,
+        executor: &Address,
+        call_contexts: &Vec<Context>,
+    ) -> Result<(), Error> {
+        Self::call_plugin_hooks(env, call_contexts, Some(executor), || {
+            vec![env, executor.into_val(env)]
+        })
+    }
+
+    fn call_plugin_hooks(
+        env: &Env,
+        auth_contexts: &Vec<Context>,
+        skip: Option<&Address>,
+        signer_keys: impl Fn() -> Vec<Val>,
+    ) -> Result<(), Error> {
+        let mut signers: Option<Vec<Val>> = None;
+
+        for (plugin, info) in installed_plugins(env).iter() {
+            if skip == Some(&plugin) {
+                continue;
+            }
+            if info.is_subscribed(PluginHook::Auth) {
+                let res = SmartAccountPluginClient::new(env, &plugin)
+                    .try_on_auth(&env.current_contract_address(), auth_contexts);
+                handle_nested_result_failure!(res, {
+                    Self::on_plugin_auth_failed(env, &plugin, info.mode)?;
+                });
+            }
+            if info.is_subscribed(PluginHook::SignedAuth) {
+                let signers = signers.get_or_insert_with(&signer_keys);
+                let res = SignerAwarePluginClient::new(env, &plugin).try_on_signed_auth(
+                    &env.current_contract_address(),
+                    auth_contexts,
+                    signers,
                 );
-                return Err(Error::PluginOnAuthFailed);
-            });
+                handle_nested_result_failure!(res, {
+                    Self::on_plugin_auth_failed(env, &plugin, info.mode)?;
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn on_plugin_auth_failed(env: &Env, plugin: &Address, mode: PluginMode) -> Result<(), Error> {
+        env.events().publish(
+            (TOPIC_PLUGIN, plugin, VERB_AUTH_FAILED),
+            PluginAuthFailedEvent {
+                plugin: plugin.clone(),
+                error: String::from_str(env, "Plugin on_auth failed"),
+            },
+        );
+        if mode == PluginMode::Enforcing {
+            return Err(Error::PluginOnAuthFailed);
         }
         Ok(())
     }
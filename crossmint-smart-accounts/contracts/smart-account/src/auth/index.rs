@@ -0,0 +1,36 @@
+//! Enumerable index of the account's signer keys.
+//!
+//! Signers live in their own persistent entries, which cannot be listed. The index keeps
+//! every stored signer key in a `StorageSet`, so that `extend_ttl` and migrations can reach
+//! all of them. Signers stored before the index existed are added by the schema 4
+//! migration.
+use soroban_sdk::{Env, Vec};
+use storage::{Namespace, Storage, StorageSet};
+
+use crate::auth::signer::SignerKey;
+use crate::config::SIGNER_INDEX_KEY;
+use crate::error::Error;
+
+pub fn signer_index() -> StorageSet<SignerKey> {
+    StorageSet::new(Namespace::Signers, SIGNER_INDEX_KEY)
+}
+
+/// Returns every indexed signer key.
+pub fn indexed_signers(env: &Env) -> Vec<SignerKey> {
+    let index = signer_index();
+    index.keys(env, 0, index.len(env))
+}
+
+/// Adds `signer_keys` to the index. Every key must belong to a stored signer. Running it
+/// again leaves storage unchanged.
+pub fn backfill_signer_index(env: &Env, signer_keys: &Vec<SignerKey>) -> Result<(), Error> {
+    let index = signer_index();
+    let persistent = Storage::persistent();
+    for key in signer_keys.iter() {
+        if !persistent.has::<SignerKey>(env, &key) {
+            return Err(Error::SignerNotFound);
+        }
+        index.insert(env, &key)?;
+    }
+    Ok(())
+}
@@ -27,8 +27,8 @@
 ///
 /// ### AuthorizationCheck
 /// Core trait for authorization checking. Implementations must provide:
-/// - `is_authorized(&self, env: &Env, context: &Context) -> bool` - Determines if an operation
-///   is authorized based on the execution context.
+/// - `is_authorized(&self, env: &Env, signer_key: &SignerKey, context: &Vec<Context>) -> bool` -
+///   Determines if an operation is authorized for the given signer based on the execution context.
 ///
 /// ### SignatureVerifier
 /// Trait for cryptographic signature verification. Implementations must provide:
@@ -50,7 +50,9 @@
 ///
 /// This allows for flexible authorization schemes from simple admin access to complex
 /// multi-signature accounts with time-based and contract-specific restrictions.
+pub mod admins;
 pub mod core;
+pub mod index;
 pub mod permissions;
 pub mod policy;
 pub mod proof;
@@ -4,17 +4,36 @@ use soroban_sdk::{
 };
 
 use crate::{
-    auth::policy::{ExternalPolicy, TimeBasedPolicy},
+    auth::{
+        policy::{ExternalPolicy, OracleSpendingLimitPolicy, TimeBasedPolicy},
+        signer::SignerKey,
+    },
     error::Error,
 };
 
 pub trait AuthorizationCheck {
-    fn is_authorized(&self, env: &Env, context: &Vec<Context>) -> bool;
+    /// Checks the contexts authorized by the signer with key `signer_key`.
+    ///
+    /// Must not write to storage: a signer whose checks pass may still be passed over in
+    /// favour of another signer of the same auth entry.
+    fn is_authorized(&self, env: &Env, signer_key: &SignerKey, context: &Vec<Context>) -> bool;
+
+    /// Records the effects of an authorization, such as consumed spending budgets. Only
+    /// called for the signer whose authorization was accepted.
+    fn on_authorized(
+        &self,
+        _env: &Env,
+        _signer_key: &SignerKey,
+        _context: &Vec<Context>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 pub trait PolicyCallback {
     fn on_add(&self, env: &Env) -> Result<(), Error>;
-    fn on_revoke(&self, env: &Env) -> Result<(), Error>;
+    /// Called when the policy is removed from the signer with key `signer_key`.
+    fn on_revoke(&self, env: &Env, signer_key: &SignerKey) -> Result<(), Error>;
 }
 
 // Main policy enum that wraps the individual policies
@@ -23,14 +42,41 @@ pub trait PolicyCallback {
 pub enum SignerPolicy {
     TimeWindowPolicy(TimeBasedPolicy),
     ExternalValidatorPolicy(ExternalPolicy),
+    OracleSpendingLimitPolicy(OracleSpendingLimitPolicy),
 }
 
 // Delegate to the specific policy implementation
 impl AuthorizationCheck for SignerPolicy {
-    fn is_authorized(&self, env: &Env, contexts: &Vec<Context>) -> bool {
+    fn is_authorized(&self, env: &Env, signer_key: &SignerKey, contexts: &Vec<Context>) -> bool {
         match self {
-            SignerPolicy::TimeWindowPolicy(policy) => policy.is_authorized(env, contexts),
-            SignerPolicy::ExternalValidatorPolicy(policy) => policy.is_authorized(env, contexts),
+            SignerPolicy::TimeWindowPolicy(policy) => {
+                policy.is_authorized(env, signer_key, contexts)
+            }
+            SignerPolicy::ExternalValidatorPolicy(policy) => {
+                policy.is_authorized(env, signer_key, contexts)
+            }
+            SignerPolicy::OracleSpendingLimitPolicy(policy) => {
+                policy.is_authorized(env, signer_key, contexts)
+            }
+        }
+    }
+
+    fn on_authorized(
+        &self,
+        env: &Env,
+        signer_key: &SignerKey,
+        contexts: &Vec<Context>,
+    ) -> Result<(), Error> {
+        match self {
+            SignerPolicy::TimeWindowPolicy(policy) => {
+                policy.on_authorized(env, signer_key, contexts)
+            }
+            SignerPolicy::ExternalValidatorPolicy(policy) => {
+                policy.on_authorized(env, signer_key, contexts)
+            }
+            SignerPolicy::OracleSpendingLimitPolicy(policy) => {
+                policy.on_authorized(env, signer_key, contexts)
+            }
         }
     }
 }
@@ -40,12 +86,14 @@ impl PolicyCallback for SignerPolicy {
         match self {
             SignerPolicy::TimeWindowPolicy(policy) => policy.on_add(env),
             SignerPolicy::ExternalValidatorPolicy(policy) => policy.on_add(env),
+            SignerPolicy::OracleSpendingLimitPolicy(policy) => policy.on_add(env),
         }
     }
-    fn on_revoke(&self, env: &Env) -> Result<(), Error> {
+    fn on_revoke(&self, env: &Env, signer_key: &SignerKey) -> Result<(), Error> {
         match self {
-            SignerPolicy::TimeWindowPolicy(policy) => policy.on_revoke(env),
-            SignerPolicy::ExternalValidatorPolicy(policy) => policy.on_revoke(env),
+            SignerPolicy::TimeWindowPolicy(policy) => policy.on_revoke(env, signer_key),
+            SignerPolicy::ExternalValidatorPolicy(policy) => policy.on_revoke(env, signer_key),
+            SignerPolicy::OracleSpendingLimitPolicy(policy) => policy.on_revoke(env, signer_key),
         }
     }
 }
@@ -67,7 +115,7 @@ pub enum SignerRole {
 // If it's a standard signer, it's authorized if the operation is not a administration operation.
 // If it's a restricted signer, it's authorized if all the policies are authorized.
 impl AuthorizationCheck for SignerRole {
-    fn is_authorized(&self, env: &Env, contexts: &Vec<Context>) -> bool {
+    fn is_authorized(&self, env: &Env, signer_key: &SignerKey, contexts: &Vec<Context>) -> bool {
         let needs_admin_approval = contexts.iter().any(|context| match context {
             Context::Contract(context) => {
                 let ContractContext { contract, .. } = context;
@@ -86,11 +134,23 @@ impl AuthorizationCheck for SignerRole {
                     // If not an admin operation, check all policies (if any)
                     policies
                         .iter()
-                        .all(|policy| policy.is_authorized(env, contexts))
+                        .all(|policy| policy.is_authorized(env, signer_key, contexts))
                 }
             }
         }
     }
-}
 
-//
+    fn on_authorized(
+        &self,
+        env: &Env,
+        signer_key: &SignerKey,
+        contexts: &Vec<Context>,
+    ) -> Result<(), Error> {
+        if let SignerRole::Standard(policies) = self {
+            for policy in policies.iter() {
+                policy.on_authorized(env, signer_key, contexts)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use crate::{
     auth::{
         permissions::{AuthorizationCheck, PolicyCallback},
         policy::interface::SmartAccountPolicyClient,
+        signer::SignerKey,
     },
     config::{TOPIC_POLICY, VERB_CALLBACK_FAILED},
     error::Error,
@@ -18,7 +19,7 @@ pub struct ExternalPolicy {
 }
 
 impl AuthorizationCheck for ExternalPolicy {
-    fn is_authorized(&self, env: &Env, contexts: &Vec<Context>) -> bool {
+    fn is_authorized(&self, env: &Env, _signer_key: &SignerKey, contexts: &Vec<Context>) -> bool {
         let wallet_address = env.current_contract_address();
         let policy_client = SmartAccountPolicyClient::new(env, &self.policy_address);
         policy_client.is_authorized(&wallet_address, contexts)
@@ -42,7 +43,7 @@ impl PolicyCallback for ExternalPolicy {
         Ok(())
     }
 
-    fn on_revoke(&self, env: &Env) -> Result<(), Error> {
+    fn on_revoke(&self, env: &Env, _signer_key: &SignerKey) -> Result<(), Error> {
         let policy_client = SmartAccountPolicyClient::new(env, &self.policy_address);
         let res = policy_client.try_on_revoke(&env.current_contract_address());
         handle_nested_result_failure!(res, {
@@ -1,8 +1,10 @@
 pub mod external;
 pub mod interface;
+pub mod spending_limit;
 pub mod time_based;
 
 pub use external::ExternalPolicy;
 pub use interface::SmartAccountPolicy;
 pub use interface::SmartAccountPolicyClient;
+pub use spending_limit::OracleSpendingLimitPolicy;
 pub use time_based::TimeBasedPolicy;
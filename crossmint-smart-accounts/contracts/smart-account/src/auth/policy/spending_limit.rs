@@ -0,0 +1,237 @@
+use smart_account_interfaces::{Asset, PriceOracleClient};
+use soroban_sdk::{
+    auth::{Context, ContractContext},
+    contracttype, symbol_short,
+    token::TokenClient,
+    xdr::ToXdr,
+    Address, BytesN, Env, Symbol, TryFromVal, Val, Vec,
+};
+use storage::{Namespace, Storage, Store};
+
+use crate::{
+    auth::{
+        permissions::{AuthorizationCheck, PolicyCallback},
+        signer::SignerKey,
+    },
+    error::Error,
+};
+
+/// Caps the value a signer can move per period, denominated in the base asset of a
+/// SEP-40 price oracle (typically USD).
+///
+/// Token amounts found in `transfer`, `transfer_from`, `approve`, `burn` and `burn_from`
+/// contexts are converted through the oracle's last price for the token. Other calls are
+/// neither counted nor denied, so token functions outside SEP-41 (such as admin functions of
+/// a token the signer controls) must be restricted by other policies.
+/// `limit` is expressed in the oracle's base asset, scaled by the oracle's `decimals()`.
+///
+/// Spending is tracked per signer and policy configuration, so signers holding identical
+/// policies each get their own budget. It is only recorded once the signer's authorization
+/// is accepted, and the window is dropped when the policy is revoked from the signer.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleSpendingLimitPolicy {
+    /// Address of the SEP-40 price oracle
+    pub oracle: Address,
+    /// Maximum value that can be spent within a period
+    pub limit: i128,
+    /// Length of the spending window, in seconds
+    pub period: u64,
+    /// Maximum age, in seconds, of a price before it is considered stale
+    pub max_price_age: u64,
+    /// Ignore transfers whose price is missing or stale instead of denying the operation.
+    /// Leaves such transfers uncapped, so it should only be set for trusted oracles.
+    pub fail_open: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpendingWindow {
+    pub started_at: u64,
+    pub spent: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpendingLimitKey {
+    /// Window of a signer for the policy with the given hash
+    Window(SignerKey, BytesN<32>),
+}
+
+/// Spending windows of the signers subject to a spending limit.
+fn spending_windows() -> Store<SpendingLimitKey, SpendingWindow> {
+    Store::new(Storage::persistent(), Namespace::Policies)
+}
+
+impl OracleSpendingLimitPolicy {
+    fn window_key(&self, env: &Env, signer_key: &SignerKey) -> SpendingLimitKey {
+        let policy_hash = env.crypto().sha256(&self.clone().to_xdr(env));
+        SpendingLimitKey::Window(signer_key.clone(), policy_hash.to_bytes())
+    }
+
+    /// Returns a fresh oracle price for the token, if one is available.
+    fn fresh_price(&self, env: &Env, token: &Address) -> Option<i128> {
+        let price = PriceOracleClient::new(env, &self.oracle)
+            .try_lastprice(&Asset::Stellar(token.clone()))
+            .ok()?
+            .ok()??;
+        let age = env.ledger().timestamp().saturating_sub(price.timestamp);
+        if price.price <= 0 || age > self.max_price_age {
+            return None;
+        }
+        Some(price.price)
+    }
+
+    fn token_decimals(env: &Env, token: &Address) -> Option<u32> {
+        TokenClient::new(env, token).try_decimals().ok()?.ok()
+    }
+
+    /// Sums the value of all spending contexts.
+    ///
+    /// Only SEP-41 functions that spend tokens are counted. Other contexts are skipped before
+    /// the oracle is queried, so calls that move no tokens cost no oracle invocation.
+    ///
+    /// Returns `None` when the operation must be denied: a price could not be resolved
+    /// outside of fail-open mode, or the computation overflowed.
+    fn total_value(&self, env: &Env, contexts: &Vec<Context>) -> Option<i128> {
+        let mut total: i128 = 0;
+        for context in contexts.iter() {
+            let Context::Contract(ContractContext {
+                contract: token,
+                fn_name,
+                args,
+            }) = context
+            else {
+                continue;
+            };
+            let Some(amount) = token_amount(env, &fn_name, &args) else {
+                continue;
+            };
+            let (price, decimals) = match (
+                self.fresh_price(env, &token),
+                Self::token_decimals(env, &token),
+            ) {
+                (Some(price), Some(decimals)) => (price, decimals),
+                _ if self.fail_open => continue,
+                _ => return None,
+            };
+            // Round up so that dust amounts cannot slip through the limit
+            let scale = 10i128.checked_pow(decimals)?;
+            let value = amount
+                .checked_mul(price)?
+                .checked_add(scale - 1)?
+                .checked_div(scale)?;
+            total = total.checked_add(value)?;
+        }
+        Some(total)
+    }
+
+    /// Computes the signer's spending window once the contexts are accounted for.
+    ///
+    /// Returns `None` when the contexts must be denied, and `Some(None)` when they spend
+    /// nothing the policy counts. Storage is left untouched.
+    fn next_window(
+        &self,
+        env: &Env,
+        signer_key: &SignerKey,
+        contexts: &Vec<Context>,
+    ) -> Option<Option<(SpendingLimitKey, SpendingWindow)>> {
+        let total = self.total_value(env, contexts)?;
+        if total == 0 {
+            return Some(None);
+        }
+
+        let key = self.window_key(env, signer_key);
+        let now = env.ledger().timestamp();
+        let window = match spending_windows().get(env, &key) {
+            Some(window) if now < window.started_at.saturating_add(self.period) => window,
+            _ => SpendingWindow {
+                started_at: now,
+                spent: 0,
+            },
+        };
+
+        let spent = window.spent.checked_add(total)?;
+        if spent > self.limit {
+            return None;
+        }
+        Some(Some((
+            key,
+            SpendingWindow {
+                started_at: window.started_at,
+                spent,
+            },
+        )))
+    }
+}
+
+/// Position of the amount in the arguments of the SEP-41 functions that spend tokens.
+fn amount_position(env: &Env, fn_name: &Symbol) -> Option<u32> {
+    if *fn_name == symbol_short!("transfer")
+        || *fn_name == symbol_short!("approve")
+        || *fn_name == symbol_short!("burn_from")
+    {
+        Some(2)
+    } else if *fn_name == Symbol::new(env, "transfer_from") {
+        Some(3)
+    } else if *fn_name == symbol_short!("burn") {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// Returns whether `fn_name` is a SEP-41 function that spends tokens.
+pub(crate) fn is_spending_function(env: &Env, fn_name: &Symbol) -> bool {
+    amount_position(env, fn_name).is_some()
+}
+
+/// Extracts the amount spent by a SEP-41 `transfer`, `transfer_from`, `approve`, `burn` or
+/// `burn_from` invocation of a token. Returns `None` for other functions and for amounts
+/// that are not positive.
+pub(crate) fn token_amount(env: &Env, fn_name: &Symbol, args: &Vec<Val>) -> Option<i128> {
+    let amount = i128::try_from_val(env, &args.get(amount_position(env, fn_name)?)?).ok()?;
+    if amount <= 0 {
+        return None;
+    }
+    Some(amount)
+}
+
+impl AuthorizationCheck for OracleSpendingLimitPolicy {
+    fn is_authorized(&self, env: &Env, signer_key: &SignerKey, contexts: &Vec<Context>) -> bool {
+        self.next_window(env, signer_key, contexts).is_some()
+    }
+
+    fn on_authorized(
+        &self,
+        env: &Env,
+        signer_key: &SignerKey,
+        contexts: &Vec<Context>,
+    ) -> Result<(), Error> {
+        let Some((key, window)) = self
+            .next_window(env, signer_key, contexts)
+            .ok_or(Error::InsufficientPermissions)?
+        else {
+            return Ok(());
+        };
+        spending_windows().set(env, &key, &window)?;
+        Ok(())
+    }
+}
+
+impl PolicyCallback for OracleSpendingLimitPolicy {
+    fn on_add(&self, _env: &Env) -> Result<(), Error> {
+        if self.limit <= 0 || self.period == 0 {
+            return Err(Error::InvalidSpendingLimit);
+        }
+        Ok(())
+    }
+    fn on_revoke(&self, env: &Env, signer_key: &SignerKey) -> Result<(), Error> {
+        let windows = spending_windows();
+        let key = self.window_key(env, signer_key);
+        if windows.has(env, &key) {
+            windows.delete(env, &key)?;
+        }
+        Ok(())
+    }
+}
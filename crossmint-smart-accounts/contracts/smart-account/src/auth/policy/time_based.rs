@@ -1,7 +1,10 @@
 use soroban_sdk::{auth::Context, contracttype, Env, Vec};
 
 use crate::{
-    auth::permissions::{AuthorizationCheck, PolicyCallback},
+    auth::{
+        permissions::{AuthorizationCheck, PolicyCallback},
+        signer::SignerKey,
+    },
     error::Error,
 };
 
@@ -13,7 +16,7 @@ pub struct TimeBasedPolicy {
 }
 
 impl AuthorizationCheck for TimeBasedPolicy {
-    fn is_authorized(&self, env: &Env, _context: &Vec<Context>) -> bool {
+    fn is_authorized(&self, env: &Env, _signer_key: &SignerKey, _context: &Vec<Context>) -> bool {
         let current_time = env.ledger().timestamp();
         current_time >= self.not_before && current_time <= self.not_after
     }
@@ -30,7 +33,7 @@ impl PolicyCallback for TimeBasedPolicy {
         }
         Ok(())
     }
-    fn on_revoke(&self, _env: &Env) -> Result<(), Error> {
+    fn on_revoke(&self, _env: &Env, _signer_key: &SignerKey) -> Result<(), Error> {
         Ok(())
     }
 }
@@ -29,12 +29,6 @@ impl SignatureVerifier for Signer {
     }
 }
 
-impl AuthorizationCheck for Signer {
-    fn is_authorized(&self, env: &Env, contexts: &Vec<Context>) -> bool {
-        self.role().is_authorized(env, contexts)
-    }
-}
-
 impl From<Signer> for SignerKey {
     fn from(signer: Signer) -> Self {
         match signer {
@@ -45,6 +39,18 @@ impl From<Signer> for SignerKey {
 }
 
 impl Signer {
+    /// Checks the contexts against the signer's role and policies.
+    pub fn is_authorized(&self, env: &Env, contexts: &Vec<Context>) -> bool {
+        self.role()
+            .is_authorized(env, &self.clone().into(), contexts)
+    }
+
+    /// Records the effects of an accepted authorization on the signer's policies.
+    pub fn on_authorized(&self, env: &Env, contexts: &Vec<Context>) -> Result<(), Error> {
+        self.role()
+            .on_authorized(env, &self.clone().into(), contexts)
+    }
+
     pub fn role(&self) -> SignerRole {
         match self {
             Signer::Ed25519(_, role) => role.clone(),
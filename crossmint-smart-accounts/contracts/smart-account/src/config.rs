@@ -1,11 +1,22 @@
 use soroban_sdk::symbol_short;
 
 pub const PLUGINS_KEY: soroban_sdk::Symbol = symbol_short!("plugins");
+pub const RECOVERY_PLUGIN_KEY: soroban_sdk::Symbol = symbol_short!("recovery");
+pub const SCHEMA_VERSION_KEY: soroban_sdk::Symbol = symbol_short!("schema");
+pub const UPGRADE_REGISTRY_KEY: soroban_sdk::Symbol = symbol_short!("upg_reg");
+// Signers of the authorization of the upgrade in progress, recorded in the upgrade history
+pub const UPGRADE_SIGNERS_KEY: soroban_sdk::Symbol = symbol_short!("upg_sign");
+// Persistent until schema 3, then instance
 pub const ADMIN_COUNT_KEY: soroban_sdk::Symbol = symbol_short!("admin_cnt");
+// Instance copy of the admin signers, see `auth::admins`
+pub const ADMIN_SIGNERS_KEY: soroban_sdk::Symbol = symbol_short!("admins");
+// Name of the signer index set, see `auth::index`
+pub const SIGNER_INDEX_KEY: soroban_sdk::Symbol = symbol_short!("signers");
 
 pub const TOPIC_SIGNER: soroban_sdk::Symbol = symbol_short!("signer");
 pub const TOPIC_PLUGIN: soroban_sdk::Symbol = symbol_short!("plugin");
 pub const TOPIC_POLICY: soroban_sdk::Symbol = symbol_short!("policy");
+pub const TOPIC_UPGRADE: soroban_sdk::Symbol = symbol_short!("upgrade");
 
 pub const VERB_ADDED: soroban_sdk::Symbol = symbol_short!("added");
 pub const VERB_UPDATED: soroban_sdk::Symbol = symbol_short!("updated");
@@ -13,5 +24,12 @@ pub const VERB_REVOKED: soroban_sdk::Symbol = symbol_short!("revoked");
 pub const VERB_INSTALLED: soroban_sdk::Symbol = symbol_short!("installed");
 pub const VERB_UNINSTALLED: soroban_sdk::Symbol = symbol_short!("uninst");
 pub const VERB_UNINSTALL_FAILED: soroban_sdk::Symbol = symbol_short!("uninsterr");
+pub const VERB_CONFIGURED: soroban_sdk::Symbol = symbol_short!("config");
+pub const VERB_SCOPE_SET: soroban_sdk::Symbol = symbol_short!("scope");
+pub const VERB_EXECUTED: soroban_sdk::Symbol = symbol_short!("executed");
+pub const VERB_RECOVERY_SET: soroban_sdk::Symbol = symbol_short!("recovery");
+pub const VERB_RECOVERED: soroban_sdk::Symbol = symbol_short!("recovered");
+pub const VERB_REGISTRY_SET: soroban_sdk::Symbol = symbol_short!("registry");
+pub const VERB_MODE_CHANGED: soroban_sdk::Symbol = symbol_short!("mode");
 pub const VERB_AUTH_FAILED: soroban_sdk::Symbol = symbol_short!("autherr");
 pub const VERB_CALLBACK_FAILED: soroban_sdk::Symbol = symbol_short!("cbfailed");
@@ -32,6 +32,8 @@ pub enum Error {
     CannotRevokeAdminSigner = 24,
     CannotDowngradeLastAdmin = 25,
     MaxSignersReached = 26,
+    /// The account already has `MAX_ADMINS` admin signers
+    MaxAdminsReached = 27,
 
     // === Authentication & Signature Errors (40-59) ===
     /// No matching signature found for the given criteria
@@ -62,6 +64,8 @@ pub enum Error {
     InvalidNotAfterTime = 82,
     /// Policy client error
     PolicyClientInitializationError = 83,
+    /// Spending limit or period must be greater than zero
+    InvalidSpendingLimit = 84,
 
     // === Plugin Errors (100-119) ===
     /// Plugin not found
@@ -72,6 +76,36 @@ pub enum Error {
     PluginInitializationFailed = 102,
     /// Plugin authentication failed
     PluginOnAuthFailed = 103,
+    /// Plugin rejected a configuration update
+    PluginConfigUpdateFailed = 104,
+    /// Plugin is not subscribed to the requested hook
+    PluginHookNotSubscribed = 105,
+    /// Plugin has not been granted an executor scope
+    ExecutorScopeNotFound = 106,
+    /// Call is outside the executor plugin's scope
+    ExecutorCallNotAllowed = 107,
+    /// Call would exceed the executor plugin's spending cap
+    ExecutorSpendingCapExceeded = 108,
+    /// Executor scope has an invalid spending cap
+    InvalidExecutorScope = 109,
+    /// Caller is not the account's designated recovery plugin
+    NotRecoveryPlugin = 110,
+    /// Recovery can only add and remove admin signers
+    InvalidRecoverySigner = 111,
+
+    // === Upgrade Errors (120-139) ===
+    /// The contract was upgraded and its storage migration has not completed yet
+    MigrationPending = 120,
+    /// Storage was written by a newer schema than this contract version supports
+    UnsupportedSchemaVersion = 121,
+    /// The upgrade registry has not published the wasm hash
+    UpgradeNotPublished = 122,
+    /// An upgrade registry is already set and cannot be changed by admins
+    UpgradeRegistryLocked = 123,
+    /// Migration data is not in the format the pending migration steps expect
+    InvalidMigrationData = 124,
+    /// The signer index misses admin signers, whose keys must be passed as migration data
+    IncompleteSignerIndex = 125,
 
     // === Generic Errors (1000+) ===
     /// Requested resource was not found
@@ -1,5 +1,7 @@
 use crate::auth::signer::{Signer, SignerKey};
-use soroban_sdk::{contracttype, Address, String};
+use crate::executor::{ExecutorCall, ExecutorScope};
+use crate::plugin::{PluginHook, PluginMode};
+use soroban_sdk::{contracttype, Address, String, Vec};
 
 #[contracttype]
 #[derive(Clone)]
@@ -53,6 +55,7 @@ impl From<Signer> for SignerRevokedEvent {
 #[derive(Clone)]
 pub struct PluginInstalledEvent {
     pub plugin: Address,
+    pub hooks: Vec<PluginHook>,
 }
 
 #[contracttype]
@@ -61,6 +64,53 @@ pub struct PluginUninstalledEvent {
     pub plugin: Address,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct PluginConfiguredEvent {
+    pub plugin: Address,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ExecutorScopeSetEvent {
+    pub plugin: Address,
+    pub scope: ExecutorScope,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PluginExecutedEvent {
+    pub plugin: Address,
+    pub calls: Vec<ExecutorCall>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct RecoveryPluginSetEvent {
+    pub plugin: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AdminsRecoveredEvent {
+    pub plugin: Address,
+    pub removed: Vec<SignerKey>,
+    pub added: Vec<SignerKey>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PluginModeChangedEvent {
+    pub plugin: Address,
+    pub mode: PluginMode,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct UpgradeRegistrySetEvent {
+    pub registry: Address,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PluginUninstallFailedEvent {
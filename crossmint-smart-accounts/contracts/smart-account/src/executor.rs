@@ -0,0 +1,203 @@
+pub use smart_account_interfaces::{
+    ExecutorCall, SmartAccountExecutor, SmartAccountExecutorClient,
+};
+
+use crate::auth::policy::spending_limit::{is_spending_function, token_amount, SpendingWindow};
+use crate::error::Error;
+use soroban_sdk::{
+    auth::{Context, ContractContext},
+    contracttype, Address, Env, Symbol, Vec,
+};
+use storage::{Namespace, Storage, Store};
+
+/// A contract an executor plugin may call, optionally restricted to some functions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowedCall {
+    pub contract: Address,
+    /// Functions the executor may invoke. An empty list allows every function.
+    pub functions: Vec<Symbol>,
+}
+
+/// Caps the amount of a token an executor plugin can move per period.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendingCap {
+    pub token: Address,
+    /// Maximum amount moved through `transfer`, `transfer_from`, `approve`, `burn` and
+    /// `burn_from` within a period. Other functions of a capped token are refused.
+    pub limit: i128,
+    /// Length of the spending window, in seconds
+    pub period: u64,
+}
+
+/// What an installed executor plugin is allowed to do on behalf of the account.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutorScope {
+    pub allowed_calls: Vec<AllowedCall>,
+    pub spending_caps: Vec<SpendingCap>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ExecutorKey {
+    Scope(Address),
+    Spent(Address, Address),
+}
+
+impl ExecutorScope {
+    pub fn validate(&self) -> Result<(), Error> {
+        for cap in self.spending_caps.iter() {
+            if cap.limit <= 0 || cap.period == 0 {
+                return Err(Error::InvalidExecutorScope);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn allows(&self, call: &ExecutorCall) -> bool {
+        self.allowed_calls.iter().any(|allowed| {
+            allowed.contract == call.contract
+                && (allowed.functions.is_empty() || allowed.functions.contains(&call.fn_name))
+        })
+    }
+
+    fn cap_for(&self, token: &Address) -> Option<SpendingCap> {
+        self.spending_caps.iter().find(|cap| cap.token == *token)
+    }
+}
+
+/// Scopes of the executor plugins, keyed by `ExecutorKey::Scope`.
+fn scopes() -> Store<ExecutorKey, ExecutorScope> {
+    Store::new(Storage::persistent(), Namespace::Executor)
+}
+
+/// Spending windows of the executor plugins, keyed by `ExecutorKey::Spent`.
+fn spending_windows() -> Store<ExecutorKey, SpendingWindow> {
+    Store::new(Storage::persistent(), Namespace::Executor)
+}
+
+/// Returns the scope granted to an executor plugin.
+pub fn executor_scope(env: &Env, plugin: &Address) -> Result<ExecutorScope, Error> {
+    scopes()
+        .get(env, &ExecutorKey::Scope(plugin.clone()))
+        .ok_or(Error::ExecutorScopeNotFound)
+}
+
+/// Stores the scope of an executor plugin, replacing any previous one.
+///
+/// Spending already recorded in the current windows is kept for tokens that remain capped,
+/// so that re-granting a scope cannot be used to reset a cap. Windows of tokens the new
+/// scope no longer caps are deleted.
+pub fn store_executor_scope(
+    env: &Env,
+    plugin: &Address,
+    scope: &ExecutorScope,
+) -> Result<(), Error> {
+    if let Ok(previous) = executor_scope(env, plugin) {
+        for cap in previous.spending_caps.iter() {
+            if scope.cap_for(&cap.token).is_none() {
+                delete_window(env, plugin, cap.token)?;
+            }
+        }
+    }
+    scopes().set(env, &ExecutorKey::Scope(plugin.clone()), scope)?;
+    Ok(())
+}
+
+/// Removes the scope of an executor plugin, if any, together with its spending windows.
+pub fn remove_executor_scope(env: &Env, plugin: &Address) -> Result<(), Error> {
+    let Ok(scope) = executor_scope(env, plugin) else {
+        return Ok(());
+    };
+    for cap in scope.spending_caps.iter() {
+        delete_window(env, plugin, cap.token)?;
+    }
+    scopes().delete(env, &ExecutorKey::Scope(plugin.clone()))?;
+    Ok(())
+}
+
+/// Extends the TTL of an executor plugin's scope and spending windows, if it has a scope.
+pub fn extend_executor_ttl(env: &Env, plugin: &Address) -> Result<(), Error> {
+    let Ok(scope) = executor_scope(env, plugin) else {
+        return Ok(());
+    };
+    scopes().extend_ttl(env, &ExecutorKey::Scope(plugin.clone()))?;
+    let windows = spending_windows();
+    for cap in scope.spending_caps.iter() {
+        let key = ExecutorKey::Spent(plugin.clone(), cap.token);
+        if windows.has(env, &key) {
+            windows.extend_ttl(env, &key)?;
+        }
+    }
+    Ok(())
+}
+
+fn delete_window(env: &Env, plugin: &Address, token: Address) -> Result<(), Error> {
+    let windows = spending_windows();
+    let key = ExecutorKey::Spent(plugin.clone(), token);
+    if windows.has(env, &key) {
+        windows.delete(env, &key)?;
+    }
+    Ok(())
+}
+
+/// Checks a call against the executor's scope and records the amount it spends.
+pub fn authorize_call(
+    env: &Env,
+    plugin: &Address,
+    scope: &ExecutorScope,
+    call: &ExecutorCall,
+) -> Result<(), Error> {
+    // Executors must not reconfigure the account they act for
+    if call.contract == env.current_contract_address() || !scope.allows(call) {
+        return Err(Error::ExecutorCallNotAllowed);
+    }
+
+    let Some(cap) = scope.cap_for(&call.contract) else {
+        return Ok(());
+    };
+    // Calls the cap cannot account for, such as `transfer_muxed`, would bypass it
+    if !is_spending_function(env, &call.fn_name) {
+        return Err(Error::ExecutorCallNotAllowed);
+    }
+    // Zero amounts, such as revoking an allowance, spend nothing
+    let Some(amount) = token_amount(env, &call.fn_name, &call.args) else {
+        return Ok(());
+    };
+
+    let windows = spending_windows();
+    let key = ExecutorKey::Spent(plugin.clone(), cap.token.clone());
+    let now = env.ledger().timestamp();
+    let window = match windows.get(env, &key) {
+        Some(window) if now < window.started_at.saturating_add(cap.period) => window,
+        _ => SpendingWindow {
+            started_at: now,
+            spent: 0,
+        },
+    };
+    let spent = match window.spent.checked_add(amount) {
+        Some(spent) if spent <= cap.limit => spent,
+        _ => return Err(Error::ExecutorSpendingCapExceeded),
+    };
+    let window = SpendingWindow {
+        started_at: window.started_at,
+        spent,
+    };
+    windows.set(env, &key, &window)?;
+    Ok(())
+}
+
+/// Returns the calls as the contract contexts plugin auth hooks receive.
+pub fn call_contexts(env: &Env, calls: &Vec<ExecutorCall>) -> Vec<Context> {
+    let mut contexts = Vec::new(env);
+    for call in calls.iter() {
+        contexts.push_back(Context::Contract(ContractContext {
+            contract: call.contract,
+            fn_name: call.fn_name,
+            args: call.args,
+        }));
+    }
+    contexts
+}
@@ -1,14 +1,18 @@
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{Address, Bytes, Env, Map, Val, Vec};
 
 use crate::auth::signer::{Signer, SignerKey};
 use crate::error::Error;
+use crate::executor::{ExecutorCall, ExecutorScope};
+use crate::migration::AccountVersion;
+use crate::plugin::{PluginInfo, PluginInstallParams, PluginMode};
+use storage::StorageEvents;
 
 /// Public API of the Smart Account contract.
 ///
 /// Provides initialization, signer management, and plugin lifecycle operations.
 pub trait SmartAccountInterface {
     /// Initializes the contract with the given signers and plugins.
-    fn __constructor(env: Env, signers: Vec<Signer>, plugins: Vec<Address>);
+    fn __constructor(env: Env, signers: Vec<Signer>, plugins: Vec<PluginInstallParams>);
     /// Adds a new signer to the account.
     fn add_signer(env: &Env, signer: Signer) -> Result<(), Error>;
     /// Updates an existing signer configuration.
@@ -19,10 +23,60 @@ pub trait SmartAccountInterface {
     fn get_signer(env: &Env, signer_key: SignerKey) -> Result<Signer, Error>;
     /// Checks if a signer exists.
     fn has_signer(env: &Env, signer_key: SignerKey) -> Result<bool, Error>;
-    /// Installs a plugin and invokes its initialization hook.
-    fn install_plugin(env: &Env, plugin: Address) -> Result<(), Error>;
+    /// Installs a plugin and invokes its initialization hook with the given config.
+    fn install_plugin(env: &Env, plugin: Address, config: Bytes) -> Result<(), Error>;
+    /// Passes a new config to an installed plugin through its `on_config` hook.
+    fn update_plugin_config(env: &Env, plugin: Address, config: Bytes) -> Result<(), Error>;
     /// Uninstalls a plugin and invokes its uninstall hook. Emits uninstall_failed on hook error.
     fn uninstall_plugin(env: &Env, plugin: Address) -> Result<(), Error>;
+    /// Uninstalls a plugin without invoking any of its hooks.
+    ///
+    /// Authorizing this call skips plugin `on_auth` hooks, so an admin can remove a plugin
+    /// that makes every authorization fail.
+    fn emergency_uninstall_plugin(env: &Env, plugin: Address) -> Result<(), Error>;
+    /// Sets whether a failing `on_auth` of the plugin vetoes authorization or is only reported.
+    fn set_plugin_mode(env: &Env, plugin: Address, mode: PluginMode) -> Result<(), Error>;
+    /// Grants an installed plugin the scope within which it can execute calls as the account.
+    fn set_executor_scope(env: &Env, plugin: Address, scope: ExecutorScope) -> Result<(), Error>;
+    /// Executes calls as the account on behalf of an executor plugin, within its scope.
+    ///
+    /// Must be invoked by the plugin itself. The calls do not go through `__check_auth`, so
+    /// the auth hooks of the other installed plugins are invoked here instead, with the
+    /// executor's address in place of signer keys.
+    fn execute_from_plugin(
+        env: &Env,
+        plugin: Address,
+        calls: Vec<ExecutorCall>,
+    ) -> Result<Vec<Val>, Error>;
+    /// Designates the installed plugin allowed to rotate admin signers, or clears it.
+    fn set_recovery_plugin(env: &Env, plugin: Option<Address>) -> Result<(), Error>;
+    /// Adds and removes admin signers on behalf of the designated recovery plugin.
+    ///
+    /// Must be invoked by the recovery plugin itself. The old admins are removed first, so
+    /// the rotation fits within `MAX_ADMINS`, and at least one admin must remain.
+    fn rotate_admins(
+        env: &Env,
+        plugin: Address,
+        remove: Vec<SignerKey>,
+        add: Vec<Signer>,
+    ) -> Result<(), Error>;
     /// Checks if a plugin is installed.
     fn is_plugin_installed(env: &Env, plugin: Address) -> bool;
+    /// Lists installed plugins with their registry metadata. Plugin wasm hashes are not
+    /// included, as the account cannot resolve them.
+    fn list_plugins(env: &Env) -> Map<Address, PluginInfo>;
+    /// Restricts upgrades to wasm hashes published by the given release registry.
+    ///
+    /// Once set, the registry cannot be changed or removed by admins.
+    fn set_upgrade_registry(env: &Env, registry: Address) -> Result<(), Error>;
+    /// Returns the release registry upgrades are restricted to, if any.
+    fn upgrade_registry(env: &Env) -> Option<Address>;
+    /// Returns the release the account runs and the schema version of its storage.
+    fn version(env: &Env) -> AccountVersion;
+    /// Extends the TTL of the account's instance, code, signers and executor scopes.
+    ///
+    /// Anyone can call it to keep the account from being archived.
+    fn extend_ttl(env: &Env) -> Result<(), Error>;
+    /// Sets which storage change events the account publishes. Disabling them saves fees.
+    fn set_storage_events(env: &Env, mode: StorageEvents) -> Result<(), Error>;
 }
@@ -6,7 +6,9 @@ pub mod config;
 pub mod constants;
 pub mod error;
 pub mod events;
+pub mod executor;
 pub mod interface;
+pub mod migration;
 pub mod plugin;
 pub mod utils;
 
@@ -16,6 +18,7 @@ pub use auth::policy::SmartAccountPolicy;
 pub use auth::proof::{SignatureProofs, SignerProof};
 pub use auth::signer::{Signer, SignerKey};
 pub use error::Error;
+pub use migration::AccountVersion;
 pub use plugin::SmartAccountPlugin;
 
 #[cfg(test)]
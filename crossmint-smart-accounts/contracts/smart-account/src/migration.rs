@@ -0,0 +1,165 @@
+//! Storage schema versioning and the migration steps run after an upgrade.
+//!
+//! `upgrade` flags the account as migrating. Until `migrate` brings the stored schema to
+//! `SCHEMA_VERSION`, the account refuses every operation except `migrate` and `upgrade`.
+use crate::auth::admins::{cache_admins, cached_admins};
+use crate::auth::index::{backfill_signer_index, indexed_signers};
+use crate::auth::signer::SignerKey;
+use crate::config::{ADMIN_COUNT_KEY, SCHEMA_VERSION_KEY};
+use crate::error::Error;
+use crate::plugin::migrate_plugin_registry;
+use soroban_sdk::{contracttype, xdr::FromXdr, Bytes, Env, String, Symbol, Vec};
+use storage::Storage;
+
+/// Storage schema written by this version of the contract.
+///
+/// - 1: plugin registry as a set of addresses
+/// - 2: plugin registry of `PluginInfo` entries
+/// - 3: admin count in instance storage, admin signers cached in instance storage
+/// - 4: every signer key in the signer index, a `StorageSet`
+/// - 5: every admin signer cached in instance storage
+pub const SCHEMA_VERSION: u32 = 5;
+
+/// Release of the contract and schema of its storage.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountVersion {
+    /// Semantic version of the running wasm
+    pub semver: String,
+    /// Schema version of the account's storage. Lower than `SCHEMA_VERSION` while a
+    /// migration is pending.
+    pub schema_version: u32,
+}
+
+/// Schema of storage without a recorded version: accounts deployed before the version was
+/// stored, including those upgraded from wasm using the plain `impl_upgradeable!` form,
+/// which leaves the migrating flag set without recording a schema.
+const UNVERSIONED_SCHEMA: u32 = 1;
+
+type MigrationStep = fn(&Env, &Bytes) -> Result<(), Error>;
+
+/// Ordered migration steps. The step at index `i` upgrades schema `i + 1` to `i + 2`.
+///
+/// Steps must be idempotent: the version is stored after each one, but a step may still
+/// run again if a later one fails and the whole migration is retried.
+///
+/// Every step receives the same migration data: empty, or the XDR-encoded `Vec<SignerKey>`
+/// of all the account's signers, see `migration_signer_keys`.
+const MIGRATIONS: [MigrationStep; (SCHEMA_VERSION - 1) as usize] = [
+    migrate_plugin_registry_step,
+    move_admin_count_step,
+    backfill_signer_index_step,
+    cache_admins_step,
+];
+
+/// Decodes the signer keys passed as migration data. Persistent entries cannot be listed,
+/// so the keys of signers stored before the signer index existed must be supplied by the
+/// caller, typically rebuilt from the account's `SignerAddedEvent`s.
+fn migration_signer_keys(env: &Env, data: &Bytes) -> Result<Vec<SignerKey>, Error> {
+    if data.is_empty() {
+        return Ok(Vec::new(env));
+    }
+    Vec::<SignerKey>::from_xdr(env, data).map_err(|_| Error::InvalidMigrationData)
+}
+
+fn migrate_plugin_registry_step(env: &Env, _data: &Bytes) -> Result<(), Error> {
+    migrate_plugin_registry(env)
+}
+
+/// Moves the admin count from persistent to instance storage. Admin signers are cached by
+/// `cache_admins_step`, once every signer is indexed.
+fn move_admin_count_step(env: &Env, _data: &Bytes) -> Result<(), Error> {
+    let persistent = Storage::persistent();
+    let Some(count) = persistent.get::<Symbol, u32>(env, &ADMIN_COUNT_KEY) else {
+        return Ok(());
+    };
+    let instance = Storage::instance();
+    if instance.has::<Symbol>(env, &ADMIN_COUNT_KEY) {
+        instance.update::<Symbol, u32>(env, &ADMIN_COUNT_KEY, &count)?;
+    } else {
+        instance.store::<Symbol, u32>(env, &ADMIN_COUNT_KEY, &count)?;
+    }
+    persistent.delete::<Symbol>(env, &ADMIN_COUNT_KEY)?;
+    Ok(())
+}
+
+/// Adds the signers supplied as migration data to the signer index, which only knows the
+/// signers added since it was introduced.
+fn backfill_signer_index_step(env: &Env, data: &Bytes) -> Result<(), Error> {
+    backfill_signer_index(env, &migration_signer_keys(env, data)?)
+}
+
+/// Caches the admins stored before the admin cache existed, so that their signatures cost
+/// no persistent reads. Runs after the index backfill, which makes every signer reachable.
+///
+/// Fails with `IncompleteSignerIndex` unless the cache holds as many admins as the admin
+/// count, so that a migration missing admin keys is refused instead of leaving admins out
+/// of the index. Missing standard signers cannot be detected.
+fn cache_admins_step(env: &Env, _data: &Bytes) -> Result<(), Error> {
+    cache_admins(env, &indexed_signers(env))?;
+    let admin_count = Storage::instance()
+        .get::<Symbol, u32>(env, &ADMIN_COUNT_KEY)
+        .unwrap_or(0);
+    if cached_admins(env).len() != admin_count {
+        return Err(Error::IncompleteSignerIndex);
+    }
+    Ok(())
+}
+
+/// Returns the schema version of the account's storage.
+pub fn schema_version(env: &Env) -> u32 {
+    Storage::instance()
+        .get::<Symbol, u32>(env, &SCHEMA_VERSION_KEY)
+        .unwrap_or(UNVERSIONED_SCHEMA)
+}
+
+pub fn account_version(env: &Env) -> AccountVersion {
+    AccountVersion {
+        semver: String::from_str(env, env!("CARGO_PKG_VERSION")),
+        schema_version: schema_version(env),
+    }
+}
+
+/// Records that storage follows the current schema. Called when the account is created.
+pub fn init_schema_version(env: &Env) -> Result<(), Error> {
+    store_schema_version(env, SCHEMA_VERSION)
+}
+
+/// Runs the steps between the stored schema and `SCHEMA_VERSION`, in order.
+///
+/// Returns the schema version storage was migrated from. Running it on up-to-date
+/// storage does nothing.
+pub fn run_migrations(env: &Env, data: &Bytes) -> Result<u32, Error> {
+    let from = schema_version(env);
+    if from > SCHEMA_VERSION {
+        return Err(Error::UnsupportedSchemaVersion);
+    }
+    for version in from..SCHEMA_VERSION {
+        MIGRATIONS[(version - 1) as usize](env, data)?;
+        store_schema_version(env, version + 1)?;
+    }
+    Ok(from)
+}
+
+/// Returns whether the account was upgraded and has not completed its migration yet.
+pub fn is_migration_pending(env: &Env) -> bool {
+    upgradeable::can_complete_migration(env)
+}
+
+/// Fails with `MigrationPending` until the migration that follows an upgrade completes.
+pub fn ensure_not_migrating(env: &Env) -> Result<(), Error> {
+    if is_migration_pending(env) {
+        return Err(Error::MigrationPending);
+    }
+    Ok(())
+}
+
+fn store_schema_version(env: &Env, version: u32) -> Result<(), Error> {
+    let storage = Storage::instance();
+    if storage.has::<Symbol>(env, &SCHEMA_VERSION_KEY) {
+        storage.update::<Symbol, u32>(env, &SCHEMA_VERSION_KEY, &version)?;
+    } else {
+        storage.store::<Symbol, u32>(env, &SCHEMA_VERSION_KEY, &version)?;
+    }
+    Ok(())
+}
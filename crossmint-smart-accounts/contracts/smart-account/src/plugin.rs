@@ -1 +1,117 @@
-pub use smart_account_interfaces::{SmartAccountPlugin, SmartAccountPluginClient};
+pub use smart_account_interfaces::{
+    ConfigurablePlugin, ConfigurablePluginClient, PluginHook, PluginHooks, PluginHooksClient,
+    PluginInstallParams, SignerAwarePlugin, SignerAwarePluginClient, SmartAccountPlugin,
+    SmartAccountPluginClient,
+};
+
+use crate::config::PLUGINS_KEY;
+use crate::error::Error;
+use soroban_sdk::{contracttype, vec, Address, Env, Map, Symbol, TryFromVal, Val, Vec};
+use storage::Storage;
+
+/// How the account reacts when a plugin's `on_auth` hook fails.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PluginMode {
+    /// A failing `on_auth` vetoes the authorization
+    Enforcing,
+    /// A failing `on_auth` only emits a `PluginAuthFailedEvent`
+    Advisory,
+}
+
+/// Registry entry of an installed plugin.
+///
+/// The plugin's wasm hash is not recorded: soroban-sdk 22 gives a contract no way to read
+/// another contract's executable, so the field could never be filled. See "Plugin Wasm
+/// Pinning" in the README.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PluginInfo {
+    /// Ledger timestamp of the installation, 0 for plugins installed before it was tracked
+    pub installed_at: u64,
+    pub mode: PluginMode,
+    /// Hooks the plugin subscribed to at install time
+    pub hooks: Vec<PluginHook>,
+}
+
+impl PluginInfo {
+    pub fn new(env: &Env, hooks: Vec<PluginHook>) -> Self {
+        PluginInfo {
+            installed_at: env.ledger().timestamp(),
+            mode: PluginMode::Enforcing,
+            hooks,
+        }
+    }
+
+    /// Entry for a plugin installed before `PluginInfo` existed: every hook, enforcing.
+    fn legacy(env: &Env) -> Self {
+        PluginInfo {
+            installed_at: 0,
+            mode: PluginMode::Enforcing,
+            hooks: all_hooks(env),
+        }
+    }
+
+    pub fn is_subscribed(&self, hook: PluginHook) -> bool {
+        self.hooks.contains(hook)
+    }
+}
+
+/// Hooks assumed for plugins that do not declare theirs. `Config` and `SignedAuth` are
+/// opt-in only.
+pub fn all_hooks(env: &Env) -> Vec<PluginHook> {
+    vec![
+        env,
+        PluginHook::Install,
+        PluginHook::Uninstall,
+        PluginHook::Auth,
+    ]
+}
+
+/// Queries the hooks a plugin implements, defaulting to all hooks when the plugin
+/// does not expose `hooks()`.
+pub fn declared_hooks(env: &Env, plugin: &Address) -> Vec<PluginHook> {
+    match PluginHooksClient::new(env, plugin).try_hooks() {
+        Ok(Ok(hooks)) => hooks,
+        _ => all_hooks(env),
+    }
+}
+
+/// Returns the plugin registry, keyed by plugin address.
+///
+/// Accounts deployed before `PluginInfo` existed store a unit value per plugin. Such
+/// entries are converted on read, so authorization keeps working between an upgrade and
+/// the storage migration.
+pub fn installed_plugins(env: &Env) -> Map<Address, PluginInfo> {
+    let raw = Storage::instance()
+        .get::<Symbol, Map<Address, Val>>(env, &PLUGINS_KEY)
+        .unwrap_or_else(|| Map::new(env));
+
+    let mut plugins = Map::new(env);
+    for (plugin, value) in raw.iter() {
+        let info =
+            PluginInfo::try_from_val(env, &value).unwrap_or_else(|_| PluginInfo::legacy(env));
+        plugins.set(plugin, info);
+    }
+    plugins
+}
+
+/// Returns the registry entry of an installed plugin.
+pub fn plugin_info(env: &Env, plugin: &Address) -> Result<PluginInfo, Error> {
+    installed_plugins(env)
+        .get(plugin.clone())
+        .ok_or(Error::PluginNotFound)
+}
+
+/// Rewrites the plugin registry with `PluginInfo` values. Running it again leaves storage
+/// unchanged.
+pub fn migrate_plugin_registry(env: &Env) -> Result<(), Error> {
+    let storage = Storage::instance();
+    let plugins = installed_plugins(env);
+    if storage.has::<Symbol>(env, &PLUGINS_KEY) {
+        storage.update::<Symbol, Map<Address, PluginInfo>>(env, &PLUGINS_KEY, &plugins)?;
+    } else {
+        storage.store::<Symbol, Map<Address, PluginInfo>>(env, &PLUGINS_KEY, &plugins)?;
+    }
+    Ok(())
+}
@@ -1,11 +1,12 @@
 #![cfg(test)]
 
-use soroban_sdk::{vec, Address, Vec};
+use soroban_sdk::{vec, Vec};
 
 use crate::{
     account::SmartAccount,
     auth::permissions::SignerRole,
     interface::SmartAccountInterface,
+    plugin::PluginInstallParams,
     tests::test_utils::{setup, Ed25519TestSigner, TestSignerTrait as _},
     error::Error,
 };
@@ -17,7 +18,7 @@ fn test_cannot_downgrade_last_admin() {
     let admin_signer = Ed25519TestSigner::generate(SignerRole::Admin);
     let contract_id = env.register(
         SmartAccount,
-        (vec![&env, admin_signer.into_signer(&env)], Vec::<Address>::new(&env)),
+        (vec![&env, admin_signer.into_signer(&env)], Vec::<PluginInstallParams>::new(&env)),
     );
 
     let downgraded = Ed25519TestSigner::from_public_key(
@@ -42,7 +43,7 @@ fn test_can_downgrade_admin_if_another_admin_exists() {
         SmartAccount,
         (
             vec![&env, admin1.into_signer(&env), admin2.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
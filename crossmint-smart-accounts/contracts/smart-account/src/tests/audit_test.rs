@@ -0,0 +1,104 @@
+#![cfg(test)]
+
+use audit_log_plugin::{
+    verifier::{contexts_hash, verify_chain},
+    AuditHead, AuditLogPlugin, AuditLogPluginClient,
+};
+use soroban_sdk::{
+    auth::Context, map, testutils::BytesN as _, vec, Address, Bytes, BytesN, Env, IntoVal, Val, Vec,
+};
+
+use crate::{
+    account::SmartAccount,
+    auth::{permissions::SignerRole, proof::SignatureProofs},
+    error::Error,
+    plugin::PluginInstallParams,
+    tests::test_utils::{get_token_auth_context, setup, Ed25519TestSigner, TestSignerTrait as _},
+};
+
+fn deploy_with_audit_log(env: &Env, admin: &Ed25519TestSigner) -> (Address, Address) {
+    let plugin_id = env.register(AuditLogPlugin, ());
+    let account_id = env.register(
+        SmartAccount,
+        (
+            vec![env, admin.into_signer(env)],
+            vec![
+                env,
+                PluginInstallParams {
+                    plugin: plugin_id.clone(),
+                    config: Bytes::new(env),
+                },
+            ],
+        ),
+    );
+    (account_id, plugin_id)
+}
+
+fn check_auth(
+    env: &Env,
+    account_id: &Address,
+    admin: &Ed25519TestSigner,
+    contexts: &Vec<Context>,
+) -> Result<(), Error> {
+    let payload = BytesN::random(env);
+    let (admin_key, admin_proof) = admin.sign(env, &payload);
+    let auth_payloads = SignatureProofs(map![env, (admin_key, admin_proof)]);
+    env.try_invoke_contract_check_auth::<Error>(
+        account_id,
+        &payload,
+        auth_payloads.into_val(env),
+        contexts,
+    )
+    .map_err(|err| match err {
+        Ok(err) => err,
+        Err(err) => panic!("{:?}", err),
+    })
+}
+
+#[test]
+fn test_each_authorization_is_chained_with_its_signers() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let (account_id, plugin_id) = deploy_with_audit_log(&env, &admin);
+    let plugin = AuditLogPluginClient::new(&env, &plugin_id);
+
+    let contexts = vec![&env, get_token_auth_context(&env)];
+    check_auth(&env, &account_id, &admin, &contexts).unwrap();
+    check_auth(&env, &account_id, &admin, &contexts).unwrap();
+
+    let head = plugin.head(&account_id);
+    assert_eq!(head.length, 2);
+
+    let entries = plugin.entries(&account_id, &0, &2);
+    let admin_key: Val = admin.sign(&env, &BytesN::random(&env)).0.into_val(&env);
+    for entry in entries.iter() {
+        assert_eq!(entry.signers, vec![&env, admin_key]);
+        assert_eq!(entry.contexts_hash, contexts_hash(&env, &contexts));
+    }
+    assert_eq!(
+        verify_chain(&env, &AuditHead::genesis(&env), &entries, &head),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_failed_authorization_is_not_logged() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let (account_id, plugin_id) = deploy_with_audit_log(&env, &admin);
+
+    let outsider = Ed25519TestSigner::generate(SignerRole::Admin);
+    let contexts = vec![&env, get_token_auth_context(&env)];
+    assert_eq!(
+        check_auth(&env, &account_id, &outsider, &contexts).unwrap_err(),
+        Error::SignerNotFound
+    );
+    assert_eq!(
+        AuditLogPluginClient::new(&env, &plugin_id)
+            .head(&account_id)
+            .length,
+        0
+    );
+}
@@ -1,5 +1,5 @@
 use soroban_sdk::Vec;
-use soroban_sdk::{map, testutils::BytesN as _, vec, Address, BytesN, IntoVal};
+use soroban_sdk::{map, testutils::BytesN as _, vec, BytesN, IntoVal};
 
 use crate::{
     account::SmartAccount,
@@ -9,6 +9,7 @@ use crate::{
         proof::{SignatureProofs, SignerProof},
     },
     error::Error,
+    plugin::PluginInstallParams,
     tests::test_utils::{
         get_token_auth_context, get_update_signer_auth_context, setup, Ed25519TestSigner,
         TestSignerTrait as _,
@@ -25,7 +26,7 @@ fn test_auth_ed25519_happy_case() {
         SmartAccount,
         (
             vec![&env, test_signer.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
     let payload = BytesN::random(&env);
@@ -48,7 +49,7 @@ fn test_auth_ed25519_no_configured_signer() {
         SmartAccount,
         (
             vec![&env, test_signer.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
     let payload = BytesN::random(&env);
@@ -78,7 +79,7 @@ fn test_auth_ed25519_wrong_signature() {
         SmartAccount,
         (
             vec![&env, test_signer.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
     let payload = BytesN::random(&env);
@@ -106,7 +107,7 @@ fn test_auth_ed25519_no_signatures() {
         SmartAccount,
         (
             vec![&env, test_signer.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
     let payload = BytesN::random(&env);
@@ -134,7 +135,7 @@ fn test_deploy_without_sufficient_permissions() {
         SmartAccount,
         (
             vec![&env, test_signer.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 }
@@ -156,7 +157,7 @@ fn test_auth_multi_signature_admin_and_standard() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -193,7 +194,7 @@ fn test_auth_multi_signature_only_admin_needed() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -225,7 +226,7 @@ fn test_auth_multi_signature_only_standard_needed() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -256,7 +257,7 @@ fn test_auth_admin_can_update_signers() {
         SmartAccount,
         (
             vec![&env, admin_signer.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -291,7 +292,7 @@ fn test_auth_standard_cannot_update_signers() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -341,7 +342,7 @@ fn test_auth_time_based_policy_within_window() {
                 admin_signer.into_signer(&env),
                 restricted_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -383,7 +384,7 @@ fn test_auth_time_based_policy_outside_window() {
                 admin_signer.into_signer(&env),
                 restricted_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -425,7 +426,7 @@ fn test_auth_mixed_valid_invalid_signatures() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -466,7 +467,7 @@ fn test_auth_multiple_contexts_partial_authorization() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -502,7 +503,7 @@ fn test_auth_idempotency() {
         SmartAccount,
         (
             vec![&env, admin_signer.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -536,7 +537,10 @@ fn test_constructor_duplicate_signers() {
     let signer2 = test_signer.into_signer(&env); // Same signer key, different instance
     env.register(
         SmartAccount,
-        (vec![&env, signer1, signer2], Vec::<Address>::new(&env)),
+        (
+            vec![&env, signer1, signer2],
+            Vec::<PluginInstallParams>::new(&env),
+        ),
     );
 }
 
@@ -549,6 +553,9 @@ fn test_constructor_different_signers_success() {
     let signer2 = test_signer2.into_signer(&env);
     env.register(
         SmartAccount,
-        (vec![&env, signer1, signer2], Vec::<Address>::new(&env)),
+        (
+            vec![&env, signer1, signer2],
+            Vec::<PluginInstallParams>::new(&env),
+        ),
     );
 }
@@ -0,0 +1,182 @@
+#![cfg(test)]
+
+use soroban_sdk::{map, testutils::BytesN as _, vec, Address, BytesN, Env, IntoVal, Vec};
+use storage::Namespace;
+
+use crate::{
+    account::SmartAccount,
+    auth::{
+        admins::{cache_admins, cached_admins, MAX_ADMINS},
+        permissions::SignerRole,
+        proof::SignatureProofs,
+        signer::{Signer, SignerKey},
+        signers::Ed25519Signer,
+    },
+    config::ADMIN_SIGNERS_KEY,
+    error::Error,
+    interface::SmartAccountInterface,
+    plugin::PluginInstallParams,
+    tests::test_utils::{get_token_auth_context, setup, Ed25519TestSigner, TestSignerTrait as _},
+};
+
+fn deploy(env: &Env, admin: &Ed25519TestSigner) -> Address {
+    env.register(
+        SmartAccount,
+        (
+            vec![env, admin.into_signer(env)],
+            Vec::<PluginInstallParams>::new(env),
+        ),
+    )
+}
+
+/// Runs `__check_auth` with a signature of `admin` and returns the CPU instructions and
+/// memory bytes it consumed.
+fn measure_check_auth(env: &Env, account_id: &Address, admin: &Ed25519TestSigner) -> (u64, u64) {
+    let payload = BytesN::random(env);
+    let (admin_key, admin_proof) = admin.sign(env, &payload);
+    let proofs = SignatureProofs(map![env, (admin_key, admin_proof)]);
+
+    let mut budget = env.cost_estimate().budget();
+    budget.reset_default();
+    env.try_invoke_contract_check_auth::<Error>(
+        account_id,
+        &payload,
+        proofs.into_val(env),
+        &vec![env, get_token_auth_context(env)],
+    )
+    .unwrap();
+    (budget.cpu_instruction_cost(), budget.memory_bytes_cost())
+}
+
+/// Adds admins until the account has `count` of them.
+fn add_admins(env: &Env, account_id: &Address, count: u32) {
+    env.mock_all_auths();
+    env.as_contract(account_id, || {
+        for _ in cached_admins(env).len()..count {
+            let signer = Ed25519TestSigner::generate(SignerRole::Admin).into_signer(env);
+            SmartAccount::add_signer(env, signer).unwrap();
+        }
+    });
+}
+
+/// Compares `__check_auth` for one admin signature before and after the admin cache.
+///
+/// The numbers are the CPU instructions and memory bytes metered by the host budget of the
+/// test environment, printed with `cargo test -- --nocapture`. They cover signature
+/// verification, storage accesses and value conversions inside the invocation. They do not
+/// model ledger read fees: on network, the layout before the cache also pays for reading the
+/// signer's persistent entry, and the cache makes the instance entry, read by every call,
+/// larger. The last row measures the cache at `MAX_ADMINS` admins.
+#[test]
+fn test_cached_admin_check_auth_is_cheaper() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    let (cached_cpu, cached_mem) = measure_check_auth(&env, &account_id, &admin);
+
+    // Layout before the cache: the admin is only found in persistent storage. Signing does
+    // not fill the cache, so this measures the lookup alone.
+    env.as_contract(&account_id, || {
+        env.storage()
+            .instance()
+            .remove(&(Namespace::Signers, ADMIN_SIGNERS_KEY));
+    });
+    let (legacy_cpu, legacy_mem) = measure_check_auth(&env, &account_id, &admin);
+    env.as_contract(&account_id, || {
+        assert!(!env
+            .storage()
+            .instance()
+            .has(&(Namespace::Signers, ADMIN_SIGNERS_KEY)));
+    });
+
+    // Refill the cache, then grow it to the cap
+    env.as_contract(&account_id, || {
+        let key: SignerKey = admin.into_signer(&env).into();
+        cache_admins(&env, &vec![&env, key]).unwrap();
+    });
+    add_admins(&env, &account_id, MAX_ADMINS);
+    let (capped_cpu, capped_mem) = measure_check_auth(&env, &account_id, &admin);
+
+    std::println!("__check_auth, one admin signature     cpu insns    mem bytes");
+    std::println!("before the cache (persistent read)   {legacy_cpu:>10} {legacy_mem:>12}");
+    std::println!("cache with 1 admin                   {cached_cpu:>10} {cached_mem:>12}");
+    std::println!(
+        "cache with {MAX_ADMINS} admins                 {capped_cpu:>10} {capped_mem:>12}"
+    );
+
+    assert!(cached_cpu < legacy_cpu);
+    assert!(cached_mem < legacy_mem);
+    assert!(cached_cpu < capped_cpu);
+}
+
+#[test]
+fn test_admin_cache_follows_signer_changes() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+    let admin_key: SignerKey = admin.into_signer(&env).into();
+
+    let second = Ed25519TestSigner::generate(SignerRole::Admin);
+    let second_key: SignerKey = second.into_signer(&env).into();
+    env.as_contract(&account_id, || {
+        SmartAccount::add_signer(&env, second.into_signer(&env)).unwrap();
+        let admins = cached_admins(&env);
+        assert!(admins.contains_key(admin_key.clone()));
+        assert!(admins.contains_key(second_key.clone()));
+    });
+
+    // A downgraded admin must no longer be accepted as an admin
+    let downgraded = Signer::Ed25519(
+        Ed25519Signer::new(second.public_key(&env)),
+        SignerRole::Standard(vec![&env]),
+    );
+    env.as_contract(&account_id, || {
+        SmartAccount::update_signer(&env, downgraded).unwrap();
+        let admins = cached_admins(&env);
+        assert!(admins.contains_key(admin_key.clone()));
+        assert!(!admins.contains_key(second_key.clone()));
+    });
+
+    // Revoking the now standard signer leaves the cache untouched
+    env.as_contract(&account_id, || {
+        SmartAccount::revoke_signer(&env, second_key.clone()).unwrap();
+        assert_eq!(cached_admins(&env).len(), 1);
+    });
+}
+
+#[test]
+fn test_admin_count_is_capped() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    add_admins(&env, &account_id, MAX_ADMINS);
+    env.as_contract(&account_id, || {
+        assert_eq!(cached_admins(&env).len(), MAX_ADMINS);
+    });
+
+    let extra = Ed25519TestSigner::generate(SignerRole::Admin);
+    env.as_contract(&account_id, || {
+        assert_eq!(
+            SmartAccount::add_signer(&env, extra.into_signer(&env)),
+            Err(Error::MaxAdminsReached)
+        );
+    });
+
+    // Promoting a standard signer counts as adding an admin
+    let standard = Ed25519TestSigner::generate(SignerRole::Standard(vec![&env]));
+    let promoted = Signer::Ed25519(
+        Ed25519Signer::new(standard.public_key(&env)),
+        SignerRole::Admin,
+    );
+    env.as_contract(&account_id, || {
+        SmartAccount::add_signer(&env, standard.into_signer(&env)).unwrap();
+        assert_eq!(
+            SmartAccount::update_signer(&env, promoted),
+            Err(Error::MaxAdminsReached)
+        );
+    });
+}
@@ -0,0 +1,361 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    auth::Context,
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Bytes, Env, IntoVal, Symbol, Val, Vec,
+};
+use storage::Namespace;
+
+use audit_log_plugin::{verifier::contexts_hash, AuditLogPlugin, AuditLogPluginClient};
+
+use crate::{
+    account::SmartAccount,
+    auth::permissions::SignerRole,
+    error::Error,
+    executor::{
+        call_contexts, AllowedCall, ExecutorCall, ExecutorKey, ExecutorScope,
+        SmartAccountExecutorClient, SpendingCap,
+    },
+    interface::SmartAccountInterface,
+    plugin::PluginInstallParams,
+    tests::test_utils::{setup, Ed25519TestSigner, TestSignerTrait as _},
+};
+
+const PERIOD: u64 = 30 * 86_400;
+
+// -----------------------------------------------------------------------------
+// Executor plugin that charges a subscription from the account it is installed on
+// -----------------------------------------------------------------------------
+
+#[contract]
+pub struct SubscriptionExecutor;
+
+#[contractimpl]
+impl SubscriptionExecutor {
+    pub fn on_install(_env: &Env, _source: Address) {}
+
+    pub fn on_uninstall(_env: &Env, _source: Address) {}
+
+    pub fn on_auth(_env: &Env, _source: Address, _contexts: Vec<Context>) {}
+
+    pub fn run(env: &Env, account: Address, calls: Vec<ExecutorCall>) -> Vec<Val> {
+        SmartAccountExecutorClient::new(env, &account)
+            .execute_from_plugin(&env.current_contract_address(), &calls)
+    }
+}
+
+/// Plugin whose `on_auth` vetoes every authorization
+#[contract]
+pub struct VetoPlugin;
+
+#[contractimpl]
+impl VetoPlugin {
+    pub fn on_install(_env: &Env, _source: Address) {}
+
+    pub fn on_uninstall(_env: &Env, _source: Address) {}
+
+    pub fn on_auth(_env: &Env, _source: Address, _contexts: Vec<Context>) {
+        panic!("on_auth vetoed");
+    }
+}
+
+struct ExecutorSetup {
+    env: Env,
+    account_id: Address,
+    executor_id: Address,
+    token: Address,
+    merchant: Address,
+}
+
+fn setup_executor() -> ExecutorSetup {
+    let env = setup();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let executor_id = env.register(SubscriptionExecutor, ());
+    let account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env)],
+            vec![
+                &env,
+                PluginInstallParams {
+                    plugin: executor_id.clone(),
+                    config: Bytes::new(&env),
+                },
+            ],
+        ),
+    );
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&account_id, &1_000);
+    let merchant = Address::generate(&env);
+
+    ExecutorSetup {
+        env,
+        account_id,
+        executor_id,
+        token,
+        merchant,
+    }
+}
+
+fn grant_scope(s: &ExecutorSetup, functions: Vec<Symbol>, limit: i128) {
+    let scope = ExecutorScope {
+        allowed_calls: vec![
+            &s.env,
+            AllowedCall {
+                contract: s.token.clone(),
+                functions,
+            },
+        ],
+        spending_caps: vec![
+            &s.env,
+            SpendingCap {
+                token: s.token.clone(),
+                limit,
+                period: PERIOD,
+            },
+        ],
+    };
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::set_executor_scope(&s.env, s.executor_id.clone(), scope)
+        })
+        .unwrap();
+    // From here on, only authorizations granted by the invocation tree itself apply
+    s.env.set_auths(&[]);
+}
+
+fn install_plugin(s: &ExecutorSetup, plugin: &Address) {
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::install_plugin(&s.env, plugin.clone(), Bytes::new(&s.env))
+        })
+        .unwrap();
+}
+
+fn transfer_call(s: &ExecutorSetup, amount: i128) -> ExecutorCall {
+    ExecutorCall {
+        contract: s.token.clone(),
+        fn_name: symbol_short!("transfer"),
+        args: (s.account_id.clone(), s.merchant.clone(), amount).into_val(&s.env),
+    }
+}
+
+fn run(s: &ExecutorSetup, calls: Vec<ExecutorCall>) -> Result<(), soroban_sdk::Error> {
+    match SubscriptionExecutorClient::new(&s.env, &s.executor_id).try_run(&s.account_id, &calls) {
+        Ok(_) => Ok(()),
+        Err(Ok(err)) => Err(err),
+        Err(Err(err)) => panic!("{:?}", err),
+    }
+}
+
+fn contract_error(err: Error) -> soroban_sdk::Error {
+    soroban_sdk::Error::from_contract_error(err as u32)
+}
+
+#[test]
+fn test_executor_transfers_within_scope() {
+    let s = setup_executor();
+    grant_scope(&s, vec![&s.env, symbol_short!("transfer")], 500);
+
+    run(&s, vec![&s.env, transfer_call(&s, 200)]).unwrap();
+
+    let token = TokenClient::new(&s.env, &s.token);
+    assert_eq!(token.balance(&s.merchant), 200);
+    assert_eq!(token.balance(&s.account_id), 800);
+}
+
+#[test]
+fn test_executor_call_outside_scope_is_rejected() {
+    let s = setup_executor();
+    grant_scope(&s, vec![&s.env, symbol_short!("transfer")], 500);
+
+    let approve = ExecutorCall {
+        contract: s.token.clone(),
+        fn_name: symbol_short!("approve"),
+        args: (s.account_id.clone(), s.merchant.clone(), 100i128, 10_000u32).into_val(&s.env),
+    };
+    assert_eq!(
+        run(&s, vec![&s.env, approve]).unwrap_err(),
+        contract_error(Error::ExecutorCallNotAllowed)
+    );
+
+    let other_contract = ExecutorCall {
+        contract: Address::generate(&s.env),
+        fn_name: symbol_short!("transfer"),
+        args: Vec::new(&s.env),
+    };
+    assert_eq!(
+        run(&s, vec![&s.env, other_contract]).unwrap_err(),
+        contract_error(Error::ExecutorCallNotAllowed)
+    );
+}
+
+#[test]
+fn test_executor_spending_cap_resets_after_period() {
+    let s = setup_executor();
+    grant_scope(&s, vec![&s.env, symbol_short!("transfer")], 500);
+
+    run(&s, vec![&s.env, transfer_call(&s, 300)]).unwrap();
+    // The whole batch is rejected once it would exceed the cap
+    assert_eq!(
+        run(
+            &s,
+            vec![&s.env, transfer_call(&s, 100), transfer_call(&s, 150)]
+        )
+        .unwrap_err(),
+        contract_error(Error::ExecutorSpendingCapExceeded)
+    );
+    let token = TokenClient::new(&s.env, &s.token);
+    assert_eq!(token.balance(&s.merchant), 300);
+
+    s.env.ledger().set_timestamp(1_000 + PERIOD);
+    run(&s, vec![&s.env, transfer_call(&s, 450)]).unwrap();
+    assert_eq!(token.balance(&s.merchant), 750);
+}
+
+#[test]
+fn test_capped_token_refuses_uncounted_calls() {
+    let s = setup_executor();
+    // Every function of the token is allowed, but the cap only accounts for spending ones
+    grant_scope(&s, Vec::new(&s.env), 500);
+
+    let muxed = ExecutorCall {
+        contract: s.token.clone(),
+        fn_name: Symbol::new(&s.env, "transfer_muxed"),
+        args: (s.account_id.clone(), s.merchant.clone(), 1_000i128).into_val(&s.env),
+    };
+    assert_eq!(
+        run(&s, vec![&s.env, muxed]).unwrap_err(),
+        contract_error(Error::ExecutorCallNotAllowed)
+    );
+
+    run(&s, vec![&s.env, transfer_call(&s, 200)]).unwrap();
+    assert_eq!(TokenClient::new(&s.env, &s.token).balance(&s.merchant), 200);
+}
+
+#[test]
+fn test_replacing_scope_deletes_windows_of_uncapped_tokens() {
+    let s = setup_executor();
+    grant_scope(&s, vec![&s.env, symbol_short!("transfer")], 500);
+    run(&s, vec![&s.env, transfer_call(&s, 300)]).unwrap();
+
+    let spent_key = (
+        Namespace::Executor,
+        ExecutorKey::Spent(s.executor_id.clone(), s.token.clone()),
+    );
+    let has_window = || {
+        s.env.as_contract(&s.account_id, || {
+            s.env.storage().persistent().has(&spent_key)
+        })
+    };
+    assert!(has_window());
+
+    // Re-granting the cap keeps the window, so the cap cannot be reset
+    s.env.mock_all_auths();
+    grant_scope(&s, vec![&s.env, symbol_short!("transfer")], 500);
+    assert!(has_window());
+
+    s.env.mock_all_auths();
+    let uncapped = ExecutorScope {
+        allowed_calls: Vec::new(&s.env),
+        spending_caps: Vec::new(&s.env),
+    };
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::set_executor_scope(&s.env, s.executor_id.clone(), uncapped)
+        })
+        .unwrap();
+    assert!(!has_window());
+}
+
+#[test]
+fn test_executor_without_scope_is_rejected() {
+    let s = setup_executor();
+    s.env.set_auths(&[]);
+
+    assert_eq!(
+        run(&s, vec![&s.env, transfer_call(&s, 1)]).unwrap_err(),
+        contract_error(Error::ExecutorScopeNotFound)
+    );
+}
+
+#[test]
+fn test_uninstall_revokes_executor_scope() {
+    let s = setup_executor();
+    grant_scope(&s, Vec::new(&s.env), 500);
+
+    s.env.mock_all_auths();
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::uninstall_plugin(&s.env, s.executor_id.clone())
+        })
+        .unwrap();
+    s.env.set_auths(&[]);
+
+    assert_eq!(
+        run(&s, vec![&s.env, transfer_call(&s, 1)]).unwrap_err(),
+        contract_error(Error::PluginNotFound)
+    );
+}
+
+#[test]
+fn test_set_executor_scope_rejects_invalid_cap() {
+    let s = setup_executor();
+    let scope = ExecutorScope {
+        allowed_calls: Vec::new(&s.env),
+        spending_caps: vec![
+            &s.env,
+            SpendingCap {
+                token: s.token.clone(),
+                limit: 0,
+                period: PERIOD,
+            },
+        ],
+    };
+    let res = s.env.as_contract(&s.account_id, || {
+        SmartAccount::set_executor_scope(&s.env, s.executor_id.clone(), scope)
+    });
+    assert_eq!(res.unwrap_err(), Error::InvalidExecutorScope);
+}
+
+#[test]
+fn test_enforcing_plugin_vetoes_executor_calls() {
+    let s = setup_executor();
+    install_plugin(&s, &s.env.register(VetoPlugin, ()));
+    grant_scope(&s, vec![&s.env, symbol_short!("transfer")], 500);
+
+    assert_eq!(
+        run(&s, vec![&s.env, transfer_call(&s, 200)]).unwrap_err(),
+        contract_error(Error::PluginOnAuthFailed)
+    );
+    assert_eq!(TokenClient::new(&s.env, &s.token).balance(&s.merchant), 0);
+}
+
+#[test]
+fn test_audit_log_records_executor_calls() {
+    let s = setup_executor();
+    let audit_log = s.env.register(AuditLogPlugin, ());
+    install_plugin(&s, &audit_log);
+    grant_scope(&s, vec![&s.env, symbol_short!("transfer")], 500);
+
+    let calls = vec![&s.env, transfer_call(&s, 200)];
+    run(&s, calls.clone()).unwrap();
+
+    let audit_log = AuditLogPluginClient::new(&s.env, &audit_log);
+    assert_eq!(audit_log.head(&s.account_id).length, 1);
+    let entry = audit_log.entries(&s.account_id, &0, &1).get(0).unwrap();
+    assert_eq!(
+        entry.contexts_hash,
+        contexts_hash(&s.env, &call_contexts(&s.env, &calls))
+    );
+    let signers: Vec<Val> = vec![&s.env, s.executor_id.into_val(&s.env)];
+    assert_eq!(entry.signers, signers);
+}
@@ -0,0 +1,318 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    auth::{Context, ContractContext},
+    map,
+    testutils::BytesN as _,
+    vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec,
+};
+use storage::Namespace;
+
+use crate::{
+    account::SmartAccount,
+    auth::{
+        admins::cached_admins,
+        index::{indexed_signers, signer_index},
+        permissions::SignerRole,
+        proof::SignatureProofs,
+        signer::SignerKey,
+    },
+    config::{ADMIN_COUNT_KEY, ADMIN_SIGNERS_KEY, SCHEMA_VERSION_KEY},
+    error::Error,
+    interface::SmartAccountInterface,
+    migration::{schema_version, SCHEMA_VERSION},
+    plugin::PluginInstallParams,
+    tests::test_utils::{get_token_auth_context, setup, Ed25519TestSigner, TestSignerTrait as _},
+};
+
+fn deploy(env: &Env, admin: &Ed25519TestSigner) -> Address {
+    env.register(
+        SmartAccount,
+        (
+            vec![env, admin.into_signer(env)],
+            Vec::<PluginInstallParams>::new(env),
+        ),
+    )
+}
+
+/// Puts the account in the state `upgrade` leaves it in, without swapping its wasm.
+fn simulate_upgrade(env: &Env, account_id: &Address) {
+    env.as_contract(account_id, || upgradeable::enable_migration(env));
+}
+
+fn account_context(env: &Env, account_id: &Address, fn_name: &str) -> Context {
+    Context::Contract(ContractContext {
+        contract: account_id.clone(),
+        fn_name: Symbol::new(env, fn_name),
+        args: (Bytes::new(env),).into_val(env),
+    })
+}
+
+fn check_auth(
+    env: &Env,
+    account_id: &Address,
+    admin: &Ed25519TestSigner,
+    context: Context,
+) -> Result<(), Error> {
+    let payload = BytesN::random(env);
+    let (admin_key, admin_proof) = admin.sign(env, &payload);
+    let auth_payloads = SignatureProofs(map![env, (admin_key, admin_proof)]);
+    env.try_invoke_contract_check_auth::<Error>(
+        account_id,
+        &payload,
+        auth_payloads.into_val(env),
+        &vec![env, context],
+    )
+    .map_err(|err| match err {
+        Ok(err) => err,
+        Err(err) => panic!("{:?}", err),
+    })
+}
+
+#[test]
+fn test_new_account_uses_current_schema() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    env.as_contract(&account_id, || {
+        assert_eq!(schema_version(&env), SCHEMA_VERSION);
+    });
+}
+
+#[test]
+fn test_pending_migration_refuses_normal_operations() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+    simulate_upgrade(&env, &account_id);
+
+    assert_eq!(
+        check_auth(&env, &account_id, &admin, get_token_auth_context(&env)).unwrap_err(),
+        Error::MigrationPending
+    );
+    check_auth(
+        &env,
+        &account_id,
+        &admin,
+        account_context(&env, &account_id, "migrate"),
+    )
+    .unwrap();
+    check_auth(
+        &env,
+        &account_id,
+        &admin,
+        account_context(&env, &account_id, "upgrade"),
+    )
+    .unwrap();
+
+    env.mock_all_auths();
+    let signer = Ed25519TestSigner::generate(SignerRole::Admin).into_signer(&env);
+    let res = env.as_contract(&account_id, || SmartAccount::add_signer(&env, signer));
+    assert_eq!(res.unwrap_err(), Error::MigrationPending);
+}
+
+#[test]
+fn test_migrate_runs_pending_steps_and_unlocks_account() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    // Storage written before the schema version was recorded
+    env.as_contract(&account_id, || {
+        env.storage().instance().remove(&SCHEMA_VERSION_KEY);
+        assert_eq!(schema_version(&env), 1);
+    });
+    simulate_upgrade(&env, &account_id);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::migrate(&env, Bytes::new(&env));
+        assert_eq!(schema_version(&env), SCHEMA_VERSION);
+    });
+
+    let signer = Ed25519TestSigner::generate(SignerRole::Admin).into_signer(&env);
+    env.as_contract(&account_id, || SmartAccount::add_signer(&env, signer))
+        .unwrap();
+    check_auth(&env, &account_id, &admin, get_token_auth_context(&env)).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1100)")]
+fn test_migrate_requires_upgrade() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::migrate(&env, Bytes::new(&env));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #121)")]
+fn test_migrate_rejects_newer_schema() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    env.as_contract(&account_id, || {
+        env.storage()
+            .instance()
+            .set(&SCHEMA_VERSION_KEY, &(SCHEMA_VERSION + 1));
+    });
+    simulate_upgrade(&env, &account_id);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::migrate(&env, Bytes::new(&env));
+    });
+}
+
+#[test]
+fn test_migrate_moves_admin_count_to_instance() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+    let admin_key: SignerKey = admin.into_signer(&env).into();
+
+    // Schema 2 layout: admin count in persistent storage, no admin cache, no signer index
+    let unindexed = unindex_signers(&env, &account_id, &Vec::new(&env));
+    env.as_contract(&account_id, || {
+        let instance = env.storage().instance();
+        let count: u32 = instance.get(&ADMIN_COUNT_KEY).unwrap();
+        instance.remove(&ADMIN_COUNT_KEY);
+        instance.remove(&(Namespace::Signers, ADMIN_SIGNERS_KEY));
+        instance.set(&SCHEMA_VERSION_KEY, &2u32);
+        env.storage().persistent().set(&ADMIN_COUNT_KEY, &count);
+    });
+    simulate_upgrade(&env, &account_id);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::migrate(&env, unindexed.to_xdr(&env));
+        assert!(!env.storage().persistent().has(&ADMIN_COUNT_KEY));
+        assert_eq!(
+            env.storage().instance().get::<_, u32>(&ADMIN_COUNT_KEY),
+            Some(1)
+        );
+        // The admin is cached by the migration, before it signs again
+        assert!(cached_admins(&env).contains_key(admin_key.clone()));
+    });
+    check_auth(&env, &account_id, &admin, get_token_auth_context(&env)).unwrap();
+
+    // It is still counted, so it cannot be downgraded
+    let downgraded = Ed25519TestSigner::from_public_key(
+        admin.public_key(&env),
+        SignerRole::Standard(vec![&env]),
+    )
+    .into_signer(&env);
+    let res = env.as_contract(&account_id, || {
+        SmartAccount::update_signer(&env, downgraded)
+    });
+    assert_eq!(res.unwrap_err(), Error::CannotDowngradeLastAdmin);
+}
+
+/// Rewinds the account to schema 3, where only signers added since the index was introduced
+/// are indexed. Keeps the signers of `kept` indexed and returns the keys of the others.
+fn unindex_signers(env: &Env, account_id: &Address, kept: &Vec<SignerKey>) -> Vec<SignerKey> {
+    env.as_contract(account_id, || {
+        let index = signer_index();
+        let mut unindexed = Vec::new(env);
+        for key in indexed_signers(env).iter() {
+            if !kept.contains(&key) {
+                index.remove(env, &key).unwrap();
+                unindexed.push_back(key);
+            }
+        }
+        env.storage().instance().set(&SCHEMA_VERSION_KEY, &3u32);
+        unindexed
+    })
+}
+
+#[test]
+fn test_migrate_backfills_signer_index() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+    let admin_key: SignerKey = admin.into_signer(&env).into();
+    let second = Ed25519TestSigner::generate(SignerRole::Standard(vec![&env])).into_signer(&env);
+    let second_key: SignerKey = second.clone().into();
+    env.as_contract(&account_id, || SmartAccount::add_signer(&env, second))
+        .unwrap();
+
+    let unindexed = unindex_signers(&env, &account_id, &vec![&env, second_key.clone()]);
+    assert_eq!(unindexed, vec![&env, admin_key.clone()]);
+    simulate_upgrade(&env, &account_id);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::migrate(&env, unindexed.to_xdr(&env));
+        assert_eq!(schema_version(&env), SCHEMA_VERSION);
+        let index = signer_index();
+        assert_eq!(index.len(&env), 2);
+        assert!(index.contains(&env, &admin_key));
+        assert!(index.contains(&env, &second_key));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #125)")]
+fn test_migrate_refuses_to_finish_without_every_admin() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+    let second = Ed25519TestSigner::generate(SignerRole::Admin).into_signer(&env);
+    env.as_contract(&account_id, || SmartAccount::add_signer(&env, second))
+        .unwrap();
+
+    // Only one of the two unindexed admins is passed as migration data
+    let unindexed = unindex_signers(&env, &account_id, &Vec::new(&env));
+    env.as_contract(&account_id, || {
+        env.storage()
+            .instance()
+            .remove(&(Namespace::Signers, ADMIN_SIGNERS_KEY));
+    });
+    simulate_upgrade(&env, &account_id);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::migrate(&env, unindexed.slice(0..1).to_xdr(&env));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_migrate_rejects_unknown_signer_keys() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+    unindex_signers(&env, &account_id, &Vec::new(&env));
+    simulate_upgrade(&env, &account_id);
+
+    let unknown: SignerKey = Ed25519TestSigner::generate(SignerRole::Admin)
+        .into_signer(&env)
+        .into();
+    env.as_contract(&account_id, || {
+        SmartAccount::migrate(&env, vec![&env, unknown].to_xdr(&env));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #124)")]
+fn test_migrate_rejects_malformed_signer_keys() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+    unindex_signers(&env, &account_id, &Vec::new(&env));
+    simulate_upgrade(&env, &account_id);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::migrate(&env, Bytes::from_array(&env, &[1, 2, 3]));
+    });
+}
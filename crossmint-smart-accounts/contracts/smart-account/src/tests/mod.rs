@@ -1,7 +1,15 @@
 #[cfg(test)]
+mod audit_test;
 mod auth_test;
+mod check_auth_cost_test;
+mod executor_test;
+mod migration_test;
 mod plugin_test;
 mod policy_test;
+mod recovery_test;
 mod secp256r1_signer_test;
 mod signer_management_test;
+mod spending_limit_test;
 mod test_utils;
+mod ttl_test;
+mod upgrade_test;
@@ -3,15 +3,20 @@
 extern crate std;
 
 use soroban_sdk::{
-    auth::Context, contract, contractimpl, symbol_short, testutils::BytesN as _, vec, Address,
-    BytesN, Env, IntoVal, Symbol, Vec,
+    auth::{Context, ContractContext},
+    contract, contractimpl, map, symbol_short,
+    testutils::{BytesN as _, Ledger as _},
+    vec, Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, Vec,
 };
 
 use crate::{
     account::SmartAccount,
     auth::{permissions::SignerRole, proof::SignatureProofs},
+    config::{PLUGINS_KEY, SCHEMA_VERSION_KEY},
     error::Error,
     interface::SmartAccountInterface,
+    migration::{schema_version, SCHEMA_VERSION},
+    plugin::{PluginHook, PluginInfo, PluginInstallParams, PluginMode},
     tests::test_utils::{get_token_auth_context, setup, Ed25519TestSigner, TestSignerTrait as _},
 };
 
@@ -44,6 +49,143 @@ impl DummyPlugin {
     }
 }
 
+// -----------------------------------------------------------------------------
+// Plugin that only subscribes to lifecycle hooks and must never receive on_auth
+// -----------------------------------------------------------------------------
+
+#[contract]
+pub struct LifecycleOnlyPlugin;
+
+#[contractimpl]
+impl LifecycleOnlyPlugin {
+    pub fn hooks(env: &Env) -> Vec<PluginHook> {
+        vec![env, PluginHook::Install, PluginHook::Uninstall]
+    }
+
+    pub fn on_install(_env: &Env, _source: Address) -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub fn on_uninstall(_env: &Env, _source: Address) -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub fn on_auth(_env: &Env, _source: Address, _contexts: Vec<Context>) {
+        panic!("on_auth must not be invoked on plugins that did not subscribe to it");
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Plugin that records the config it receives at install time and on updates
+// -----------------------------------------------------------------------------
+
+const CONFIG: Symbol = symbol_short!("config");
+
+#[contract]
+pub struct ConfigurablePlugin;
+
+#[contractimpl]
+impl ConfigurablePlugin {
+    pub fn hooks(env: &Env) -> Vec<PluginHook> {
+        vec![
+            env,
+            PluginHook::Install,
+            PluginHook::Uninstall,
+            PluginHook::Auth,
+            PluginHook::Config,
+        ]
+    }
+
+    pub fn on_install(_env: &Env, _source: Address) {
+        panic!("on_install must not be invoked on plugins subscribed to Config");
+    }
+
+    pub fn on_install_with_config(env: &Env, _source: Address, config: Bytes) -> Result<(), Error> {
+        env.storage().instance().set(&CONFIG, &config);
+        Ok(())
+    }
+
+    pub fn on_config(env: &Env, _source: Address, config: Bytes) -> Result<(), Error> {
+        if config.is_empty() {
+            return Err(Error::PluginConfigUpdateFailed);
+        }
+        env.storage().instance().set(&CONFIG, &config);
+        Ok(())
+    }
+
+    pub fn on_uninstall(_env: &Env, _source: Address) -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub fn on_auth(_env: &Env, _source: Address, _contexts: Vec<Context>) {}
+
+    pub fn get_config(env: &Env) -> Option<Bytes> {
+        env.storage().instance().get(&CONFIG)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Plugin whose on_auth and on_uninstall always revert
+// -----------------------------------------------------------------------------
+
+#[contract]
+pub struct RevertingPlugin;
+
+#[contractimpl]
+impl RevertingPlugin {
+    pub fn on_install(_env: &Env, _source: Address) -> Result<(), Error> {
+        Ok(())
+    }
+
+    pub fn on_uninstall(_env: &Env, _source: Address) {
+        panic!("on_uninstall reverted");
+    }
+
+    pub fn on_auth(_env: &Env, _source: Address, _contexts: Vec<Context>) {
+        panic!("on_auth reverted");
+    }
+}
+
+fn install_params(env: &Env, plugin: &Address) -> PluginInstallParams {
+    PluginInstallParams {
+        plugin: plugin.clone(),
+        config: Bytes::new(env),
+    }
+}
+
+fn deploy_with_reverting_plugin(env: &Env, admin: &Ed25519TestSigner) -> (Address, Address) {
+    let plugin_id = env.register(RevertingPlugin, ());
+    let smart_account_id = env.register(
+        SmartAccount,
+        (
+            vec![env, admin.into_signer(env)],
+            vec![env, install_params(env, &plugin_id)],
+        ),
+    );
+    (smart_account_id, plugin_id)
+}
+
+fn check_auth_with_contexts(
+    env: &Env,
+    smart_account_id: &Address,
+    admin: &Ed25519TestSigner,
+    contexts: Vec<Context>,
+) -> Result<(), Error> {
+    let payload = BytesN::random(env);
+    let (admin_key, admin_proof) = admin.sign(env, &payload);
+    let auth_payloads = SignatureProofs(soroban_sdk::map![env, (admin_key, admin_proof)]);
+    env.try_invoke_contract_check_auth::<Error>(
+        smart_account_id,
+        &payload,
+        auth_payloads.into_val(env),
+        &contexts,
+    )
+    .map_err(|err| match err {
+        Ok(err) => err,
+        Err(err) => panic!("{:?}", err),
+    })
+}
+
 // -----------------------------------------------------------------------------
 // Test: Uninstall properly persists removal, plugin no longer receives on_auth
 // -----------------------------------------------------------------------------
@@ -59,7 +201,7 @@ fn test_uninstall_plugin_persists_removal() {
         SmartAccount,
         (
             vec![&env, admin.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -68,7 +210,7 @@ fn test_uninstall_plugin_persists_removal() {
 
     // Install plugin
     env.as_contract(&smart_account_id, || {
-        SmartAccount::install_plugin(&env, plugin_id.clone())
+        SmartAccount::install_plugin(&env, plugin_id.clone(), Bytes::new(&env))
     })
     .unwrap();
 
@@ -131,3 +273,397 @@ fn test_uninstall_plugin_persists_removal() {
         "Plugin should NOT receive on_auth after uninstall"
     );
 }
+
+// -----------------------------------------------------------------------------
+// Test: Only plugins subscribed to the auth hook receive on_auth
+// -----------------------------------------------------------------------------
+
+#[test]
+fn test_on_auth_only_invoked_for_subscribers() {
+    let env = setup();
+    env.mock_all_auths();
+
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let legacy_plugin_id = env.register(DummyPlugin, ());
+    let lifecycle_plugin_id = env.register(LifecycleOnlyPlugin, ());
+    let smart_account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env)],
+            vec![
+                &env,
+                install_params(&env, &legacy_plugin_id),
+                install_params(&env, &lifecycle_plugin_id),
+            ],
+        ),
+    );
+
+    let payload = BytesN::random(&env);
+    let (admin_key, admin_proof) = admin.sign(&env, &payload);
+    let auth_payloads = SignatureProofs(soroban_sdk::map![&env, (admin_key, admin_proof)]);
+
+    // The lifecycle-only plugin panics in on_auth, so this only succeeds if it is skipped
+    env.try_invoke_contract_check_auth::<Error>(
+        &smart_account_id,
+        &payload,
+        auth_payloads.into_val(&env),
+        &vec![&env, get_token_auth_context(&env)],
+    )
+    .unwrap();
+
+    // Plugins that do not declare hooks keep receiving every hook
+    let count = env.as_contract(&legacy_plugin_id, || DummyPlugin::get_count(&env));
+    assert_eq!(count, 1, "Legacy plugin should have received on_auth call");
+
+    env.as_contract(&smart_account_id, || {
+        SmartAccount::uninstall_plugin(&env, lifecycle_plugin_id.clone())
+    })
+    .unwrap();
+    assert!(!env.as_contract(&smart_account_id, || {
+        SmartAccount::is_plugin_installed(&env, lifecycle_plugin_id.clone())
+    }));
+}
+
+// -----------------------------------------------------------------------------
+// Test: Enforcing plugins veto authorization, advisory plugins only report
+// -----------------------------------------------------------------------------
+
+#[test]
+fn test_enforcing_plugin_failure_vetoes_auth() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let (smart_account_id, _plugin_id) = deploy_with_reverting_plugin(&env, &admin);
+
+    let res = check_auth_with_contexts(
+        &env,
+        &smart_account_id,
+        &admin,
+        vec![&env, get_token_auth_context(&env)],
+    );
+    assert_eq!(res.unwrap_err(), Error::PluginOnAuthFailed);
+}
+
+#[test]
+fn test_advisory_plugin_failure_does_not_veto_auth() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let (smart_account_id, plugin_id) = deploy_with_reverting_plugin(&env, &admin);
+
+    env.mock_all_auths();
+    env.as_contract(&smart_account_id, || {
+        SmartAccount::set_plugin_mode(&env, plugin_id.clone(), PluginMode::Advisory)
+    })
+    .unwrap();
+
+    check_auth_with_contexts(
+        &env,
+        &smart_account_id,
+        &admin,
+        vec![&env, get_token_auth_context(&env)],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_set_plugin_mode_for_unknown_plugin_fails() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let smart_account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env)],
+            Vec::<PluginInstallParams>::new(&env),
+        ),
+    );
+    let plugin_id = env.register(DummyPlugin, ());
+
+    let res = env.as_contract(&smart_account_id, || {
+        SmartAccount::set_plugin_mode(&env, plugin_id.clone(), PluginMode::Advisory)
+    });
+    assert_eq!(res.unwrap_err(), Error::PluginNotFound);
+}
+
+// -----------------------------------------------------------------------------
+// Test: Emergency uninstall recovers from a plugin that bricks __check_auth
+// -----------------------------------------------------------------------------
+
+#[test]
+fn test_emergency_uninstall_skips_plugin_hooks() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let (smart_account_id, plugin_id) = deploy_with_reverting_plugin(&env, &admin);
+
+    // Authorizing the emergency uninstall does not invoke the failing on_auth hook
+    let emergency_context = Context::Contract(ContractContext {
+        contract: smart_account_id.clone(),
+        fn_name: Symbol::new(&env, "emergency_uninstall_plugin"),
+        args: (plugin_id.clone(),).into_val(&env),
+    });
+    check_auth_with_contexts(
+        &env,
+        &smart_account_id,
+        &admin,
+        vec![&env, emergency_context],
+    )
+    .unwrap();
+
+    // Removal does not invoke the failing on_uninstall hook either
+    env.mock_all_auths();
+    env.as_contract(&smart_account_id, || {
+        SmartAccount::emergency_uninstall_plugin(&env, plugin_id.clone())
+    })
+    .unwrap();
+    assert!(!env.as_contract(&smart_account_id, || {
+        SmartAccount::is_plugin_installed(&env, plugin_id.clone())
+    }));
+
+    check_auth_with_contexts(
+        &env,
+        &smart_account_id,
+        &admin,
+        vec![&env, get_token_auth_context(&env)],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_emergency_bypass_does_not_apply_to_other_contexts() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let (smart_account_id, plugin_id) = deploy_with_reverting_plugin(&env, &admin);
+
+    let emergency_context = Context::Contract(ContractContext {
+        contract: smart_account_id.clone(),
+        fn_name: Symbol::new(&env, "emergency_uninstall_plugin"),
+        args: (plugin_id,).into_val(&env),
+    });
+    let res = check_auth_with_contexts(
+        &env,
+        &smart_account_id,
+        &admin,
+        vec![&env, emergency_context, get_token_auth_context(&env)],
+    );
+    assert_eq!(res.unwrap_err(), Error::PluginOnAuthFailed);
+}
+
+// -----------------------------------------------------------------------------
+// Test: Install-time config reaches on_install, updates reach on_config
+// -----------------------------------------------------------------------------
+
+#[test]
+fn test_install_passes_config_to_plugin() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let plugin_id = env.register(ConfigurablePlugin, ());
+    let config = Bytes::from_array(&env, &[1, 2, 3]);
+    env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env)],
+            vec![
+                &env,
+                PluginInstallParams {
+                    plugin: plugin_id.clone(),
+                    config: config.clone(),
+                },
+            ],
+        ),
+    );
+
+    let stored = env.as_contract(&plugin_id, || ConfigurablePlugin::get_config(&env));
+    assert_eq!(stored, Some(config));
+}
+
+#[test]
+fn test_install_rejects_config_for_plugin_without_config_hook() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let legacy_plugin_id = env.register(DummyPlugin, ());
+    let smart_account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env)],
+            Vec::<PluginInstallParams>::new(&env),
+        ),
+    );
+
+    // Legacy plugins are not subscribed to Config, so the config would be lost
+    let res = env.as_contract(&smart_account_id, || {
+        SmartAccount::install_plugin(
+            &env,
+            legacy_plugin_id.clone(),
+            Bytes::from_array(&env, &[1]),
+        )
+    });
+    assert_eq!(res.unwrap_err(), Error::PluginHookNotSubscribed);
+
+    // Without a config they are installed through the original on_install
+    env.as_contract(&smart_account_id, || {
+        SmartAccount::install_plugin(&env, legacy_plugin_id.clone(), Bytes::new(&env))
+    })
+    .unwrap();
+    let plugins = env.as_contract(&smart_account_id, || SmartAccount::list_plugins(&env));
+    assert!(!plugins
+        .get(legacy_plugin_id)
+        .unwrap()
+        .is_subscribed(PluginHook::Config));
+}
+
+#[test]
+fn test_update_plugin_config_invokes_on_config() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let plugin_id = env.register(ConfigurablePlugin, ());
+    let smart_account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env)],
+            vec![&env, install_params(&env, &plugin_id)],
+        ),
+    );
+
+    let new_config = Bytes::from_array(&env, &[4, 5]);
+    env.as_contract(&smart_account_id, || {
+        SmartAccount::update_plugin_config(&env, plugin_id.clone(), new_config.clone())
+    })
+    .unwrap();
+    let stored = env.as_contract(&plugin_id, || ConfigurablePlugin::get_config(&env));
+    assert_eq!(stored, Some(new_config));
+
+    // A config rejected by the plugin fails the update
+    let res = env.as_contract(&smart_account_id, || {
+        SmartAccount::update_plugin_config(&env, plugin_id.clone(), Bytes::new(&env))
+    });
+    assert_eq!(res.unwrap_err(), Error::PluginConfigUpdateFailed);
+}
+
+#[test]
+fn test_update_plugin_config_requires_subscription() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let lifecycle_plugin_id = env.register(LifecycleOnlyPlugin, ());
+    let unknown_plugin_id = env.register(ConfigurablePlugin, ());
+    let smart_account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env)],
+            vec![&env, install_params(&env, &lifecycle_plugin_id)],
+        ),
+    );
+
+    let res = env.as_contract(&smart_account_id, || {
+        SmartAccount::update_plugin_config(&env, lifecycle_plugin_id.clone(), Bytes::new(&env))
+    });
+    assert_eq!(res.unwrap_err(), Error::PluginHookNotSubscribed);
+
+    let res = env.as_contract(&smart_account_id, || {
+        SmartAccount::update_plugin_config(&env, unknown_plugin_id.clone(), Bytes::new(&env))
+    });
+    assert_eq!(res.unwrap_err(), Error::PluginNotFound);
+}
+
+// -----------------------------------------------------------------------------
+// Test: Plugin registry metadata and migration of the legacy registry
+// -----------------------------------------------------------------------------
+
+#[test]
+fn test_list_plugins_returns_registry_metadata() {
+    let env = setup();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let legacy_plugin_id = env.register(DummyPlugin, ());
+    let lifecycle_plugin_id = env.register(LifecycleOnlyPlugin, ());
+    let smart_account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env)],
+            vec![&env, install_params(&env, &legacy_plugin_id)],
+        ),
+    );
+
+    env.ledger().set_timestamp(2_000);
+    env.as_contract(&smart_account_id, || {
+        SmartAccount::install_plugin(&env, lifecycle_plugin_id.clone(), Bytes::new(&env)).unwrap();
+        SmartAccount::set_plugin_mode(&env, lifecycle_plugin_id.clone(), PluginMode::Advisory)
+            .unwrap();
+    });
+
+    let plugins = env.as_contract(&smart_account_id, || SmartAccount::list_plugins(&env));
+    assert_eq!(plugins.len(), 2);
+    assert_eq!(
+        plugins.get(legacy_plugin_id).unwrap(),
+        PluginInfo {
+            installed_at: 1_000,
+            mode: PluginMode::Enforcing,
+            hooks: vec![
+                &env,
+                PluginHook::Install,
+                PluginHook::Uninstall,
+                PluginHook::Auth,
+            ],
+        }
+    );
+    assert_eq!(
+        plugins.get(lifecycle_plugin_id).unwrap(),
+        PluginInfo {
+            installed_at: 2_000,
+            mode: PluginMode::Advisory,
+            hooks: vec![&env, PluginHook::Install, PluginHook::Uninstall],
+        }
+    );
+}
+
+#[test]
+fn test_migrate_converts_legacy_plugin_registry() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let plugin_id = env.register(DummyPlugin, ());
+    let smart_account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env)],
+            Vec::<PluginInstallParams>::new(&env),
+        ),
+    );
+
+    // Rewrite the registry in the layout used before PluginInfo existed
+    env.as_contract(&smart_account_id, || {
+        let instance = env.storage().instance();
+        instance.remove(&SCHEMA_VERSION_KEY);
+        instance.set(&PLUGINS_KEY, &map![&env, (plugin_id.clone(), ())]);
+    });
+
+    let expected = PluginInfo {
+        installed_at: 0,
+        mode: PluginMode::Enforcing,
+        hooks: vec![
+            &env,
+            PluginHook::Install,
+            PluginHook::Uninstall,
+            PluginHook::Auth,
+        ],
+    };
+
+    // Legacy entries are readable before the migration runs
+    let plugins = env.as_contract(&smart_account_id, || SmartAccount::list_plugins(&env));
+    assert_eq!(plugins.get(plugin_id.clone()).unwrap(), expected);
+
+    env.as_contract(&smart_account_id, || {
+        upgradeable::enable_migration(&env);
+        SmartAccount::migrate(&env, Bytes::new(&env));
+    });
+
+    env.as_contract(&smart_account_id, || {
+        let instance = env.storage().instance();
+        let stored: Map<Address, PluginInfo> = instance.get(&PLUGINS_KEY).unwrap();
+        assert_eq!(stored.get(plugin_id.clone()).unwrap(), expected);
+        assert_eq!(schema_version(&env), SCHEMA_VERSION);
+    });
+}
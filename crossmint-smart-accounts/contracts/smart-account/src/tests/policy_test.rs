@@ -12,6 +12,7 @@ use crate::auth::permissions::{SignerPolicy, SignerRole};
 use crate::auth::policy::{ExternalPolicy, TimeBasedPolicy};
 use crate::auth::signer::{Signer, SignerKey};
 use crate::error::Error;
+use crate::plugin::PluginInstallParams;
 use crate::tests::test_utils::{setup, Ed25519TestSigner};
 
 #[contract]
@@ -82,7 +83,7 @@ fn test_deploy_with_time_based_policy() {
         SmartAccount,
         (
             vec![&env, admin_signer, test_signer],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 }
@@ -102,7 +103,7 @@ fn test_deploy_with_time_based_policy_wrong_time_range() {
         SmartAccount,
         (
             vec![&env, admin_signer, test_signer],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 }
@@ -122,7 +123,7 @@ fn test_deploy_with_time_based_policy_wrong_not_after() {
         SmartAccount,
         (
             vec![&env, admin_signer, test_signer],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 }
@@ -142,7 +143,7 @@ fn test_signer_with_external_policy() {
         SmartAccount,
         (
             vec![&env, admin_signer, test_signer],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 }
@@ -162,7 +163,7 @@ fn test_add_signer_with_external_polic_calls_on_add() {
         SmartAccount,
         (
             vec![&env, admin_signer, test_signer],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
     ensure_policy_event_is_emmited(&env, policy_id, symbol_short!("ON_ADD"));
@@ -183,7 +184,7 @@ fn test_revoke_signer_with_external_polic_calls_on_revoke() {
         SmartAccount,
         (
             vec![&env, admin_signer, test_signer.clone()],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
     env.mock_all_auths();
@@ -225,7 +226,7 @@ fn test_update_signer_with_external_polic_lifecycle() {
             SmartAccount,
             (
                 vec![&env, admin_signer, test_signer_1.clone()],
-                Vec::<Address>::new(&env),
+                Vec::<PluginInstallParams>::new(&env),
             ),
         );
         env.mock_all_auths();
@@ -259,7 +260,7 @@ fn test_update_signer_with_external_polic_lifecycle_with_same_policy() {
         SmartAccount,
         (
             vec![&env, admin_signer, test_signer_1.clone()],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
     env.mock_all_auths();
@@ -0,0 +1,246 @@
+#![cfg(test)]
+
+use social_recovery_plugin::{
+    RecoveryConfig, RecoveryError, SocialRecoveryPlugin, SocialRecoveryPluginClient,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec,
+    xdr::ToXdr,
+    Address, Env, IntoVal, Val, Vec,
+};
+
+use crate::{
+    account::SmartAccount,
+    auth::{
+        admins::MAX_ADMINS,
+        permissions::SignerRole,
+        signer::{Signer, SignerKey},
+    },
+    error::Error,
+    interface::SmartAccountInterface,
+    plugin::PluginInstallParams,
+    tests::test_utils::{setup, Ed25519TestSigner, TestSignerTrait as _},
+};
+
+const DELAY: u64 = 86_400;
+
+struct RecoverySetup {
+    env: Env,
+    account_id: Address,
+    plugin: SocialRecoveryPluginClient<'static>,
+    guardians: Vec<Address>,
+    lost_admin: Signer,
+    new_admin: Signer,
+}
+
+fn setup_recovery() -> RecoverySetup {
+    let env = setup();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let lost_admin = Ed25519TestSigner::generate(SignerRole::Admin).into_signer(&env);
+    let new_admin = Ed25519TestSigner::generate(SignerRole::Admin).into_signer(&env);
+    let guardians = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let config = RecoveryConfig {
+        guardians: guardians.clone(),
+        threshold: 2,
+        delay: DELAY,
+    };
+
+    let plugin_id = env.register(SocialRecoveryPlugin, ());
+    let account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, lost_admin.clone()],
+            vec![
+                &env,
+                PluginInstallParams {
+                    plugin: plugin_id.clone(),
+                    config: config.to_xdr(&env),
+                },
+            ],
+        ),
+    );
+    env.as_contract(&account_id, || {
+        SmartAccount::set_recovery_plugin(&env, Some(plugin_id.clone()))
+    })
+    .unwrap();
+
+    let plugin = SocialRecoveryPluginClient::new(&env, &plugin_id);
+    RecoverySetup {
+        env,
+        account_id,
+        plugin,
+        guardians,
+        lost_admin,
+        new_admin,
+    }
+}
+
+fn propose_rotation(s: &RecoverySetup) {
+    let lost_admin_key: SignerKey = s.lost_admin.clone().into();
+    let remove: Vec<Val> = vec![&s.env, lost_admin_key.into_val(&s.env)];
+    let add: Vec<Val> = vec![&s.env, s.new_admin.into_val(&s.env)];
+    s.plugin
+        .propose(&s.account_id, &s.guardians.get(0).unwrap(), &remove, &add);
+}
+
+fn has_signer(s: &RecoverySetup, signer: &Signer) -> bool {
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::has_signer(&s.env, signer.clone().into())
+        })
+        .unwrap()
+}
+
+fn recovery_error(err: RecoveryError) -> soroban_sdk::Error {
+    err.into()
+}
+
+#[test]
+fn test_guardians_rotate_admins_after_delay() {
+    let s = setup_recovery();
+    propose_rotation(&s);
+    s.plugin
+        .approve(&s.account_id, &s.guardians.get(1).unwrap());
+
+    // The threshold is reached but the delay has not elapsed yet
+    assert_eq!(
+        s.plugin.try_execute(&s.account_id).unwrap_err().unwrap(),
+        recovery_error(RecoveryError::DelayNotElapsed)
+    );
+
+    s.env.ledger().set_timestamp(1_000 + DELAY);
+    s.plugin.execute(&s.account_id);
+
+    assert!(has_signer(&s, &s.new_admin));
+    assert!(!has_signer(&s, &s.lost_admin));
+    assert!(s.plugin.get_proposal(&s.account_id).is_none());
+}
+
+#[test]
+fn test_rotate_admins_at_the_admin_cap() {
+    let s = setup_recovery();
+    for _ in 1..MAX_ADMINS {
+        let admin = Ed25519TestSigner::generate(SignerRole::Admin).into_signer(&s.env);
+        s.env
+            .as_contract(&s.account_id, || SmartAccount::add_signer(&s.env, admin))
+            .unwrap();
+    }
+
+    // The lost admin is removed before the new one is added, so the cap is not exceeded
+    propose_rotation(&s);
+    s.plugin
+        .approve(&s.account_id, &s.guardians.get(1).unwrap());
+    s.env.ledger().set_timestamp(1_000 + DELAY);
+    s.plugin.execute(&s.account_id);
+
+    assert!(has_signer(&s, &s.new_admin));
+    assert!(!has_signer(&s, &s.lost_admin));
+}
+
+#[test]
+fn test_rotate_admins_keeps_an_admin() {
+    let s = setup_recovery();
+    let lost_admin_key: SignerKey = s.lost_admin.clone().into();
+    let res = s.env.as_contract(&s.account_id, || {
+        SmartAccount::rotate_admins(
+            &s.env,
+            s.plugin.address.clone(),
+            vec![&s.env, lost_admin_key],
+            Vec::new(&s.env),
+        )
+    });
+    assert_eq!(res.unwrap_err(), Error::CannotDowngradeLastAdmin);
+}
+
+#[test]
+fn test_recovery_requires_threshold() {
+    let s = setup_recovery();
+    propose_rotation(&s);
+    s.env.ledger().set_timestamp(1_000 + DELAY);
+
+    assert_eq!(
+        s.plugin.try_execute(&s.account_id).unwrap_err().unwrap(),
+        recovery_error(RecoveryError::ThresholdNotReached)
+    );
+    assert!(has_signer(&s, &s.lost_admin));
+}
+
+#[test]
+fn test_non_guardian_cannot_approve() {
+    let s = setup_recovery();
+    propose_rotation(&s);
+
+    assert_eq!(
+        s.plugin
+            .try_approve(&s.account_id, &Address::generate(&s.env))
+            .unwrap_err()
+            .unwrap(),
+        recovery_error(RecoveryError::NotGuardian)
+    );
+}
+
+#[test]
+fn test_account_can_cancel_pending_recovery() {
+    let s = setup_recovery();
+    propose_rotation(&s);
+    s.plugin
+        .approve(&s.account_id, &s.guardians.get(1).unwrap());
+
+    s.plugin.cancel(&s.account_id);
+    s.env.ledger().set_timestamp(1_000 + DELAY);
+
+    assert_eq!(
+        s.plugin.try_execute(&s.account_id).unwrap_err().unwrap(),
+        recovery_error(RecoveryError::NoProposal)
+    );
+    assert!(has_signer(&s, &s.lost_admin));
+}
+
+#[test]
+fn test_only_recovery_plugin_can_rotate_admins() {
+    let s = setup_recovery();
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::set_recovery_plugin(&s.env, None)
+        })
+        .unwrap();
+
+    let res = s.env.as_contract(&s.account_id, || {
+        SmartAccount::rotate_admins(
+            &s.env,
+            s.plugin.address.clone(),
+            Vec::new(&s.env),
+            vec![&s.env, s.new_admin.clone()],
+        )
+    });
+    assert_eq!(res.unwrap_err(), Error::NotRecoveryPlugin);
+}
+
+#[test]
+fn test_uninstall_clears_recovery_plugin() {
+    let s = setup_recovery();
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::uninstall_plugin(&s.env, s.plugin.address.clone())
+        })
+        .unwrap();
+    assert!(s.plugin.get_config(&s.account_id).is_none());
+
+    let res = s.env.as_contract(&s.account_id, || {
+        SmartAccount::rotate_admins(
+            &s.env,
+            s.plugin.address.clone(),
+            Vec::new(&s.env),
+            vec![&s.env, s.new_admin.clone()],
+        )
+    });
+    assert_eq!(res.unwrap_err(), Error::NotRecoveryPlugin);
+}
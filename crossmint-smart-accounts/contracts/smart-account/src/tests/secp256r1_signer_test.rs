@@ -10,7 +10,7 @@ use base64ct::{Base64UrlUnpadded, Encoding};
 use p256::ecdsa::{signature::Signer as P256Signer, Signature, SigningKey, VerifyingKey};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
-use soroban_sdk::{map, vec, Address, Bytes, BytesN, Env, IntoVal, Vec as SorobanVec};
+use soroban_sdk::{map, vec, Bytes, BytesN, Env, IntoVal, Vec as SorobanVec};
 
 // Internal crate imports
 use crate::account::SmartAccount;
@@ -20,6 +20,7 @@ use crate::auth::signer::{Signer, SignerKey};
 use crate::auth::signers::secp256r1::Secp256r1Signer;
 use crate::auth::signers::SignatureVerifier;
 use crate::error::Error;
+use crate::plugin::PluginInstallParams;
 use crate::tests::test_utils::get_token_auth_context;
 
 // Helper functions for WebAuthn testing
@@ -197,7 +198,10 @@ fn test_secp256r1_end_to_end_smart_account_auth() {
     let signer = Signer::Secp256r1(test_data.signer.clone(), SignerRole::Admin);
     let contract_id = env.register(
         SmartAccount,
-        (vec![&env, signer], SorobanVec::<Address>::new(&env)),
+        (
+            vec![&env, signer],
+            SorobanVec::<PluginInstallParams>::new(&env),
+        ),
     );
 
     // Use the same payload and proof from test_data
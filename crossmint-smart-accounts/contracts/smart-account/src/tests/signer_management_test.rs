@@ -1,12 +1,13 @@
 #![cfg(test)]
 
-use soroban_sdk::{map, testutils::BytesN as _, vec, Address, BytesN, Vec};
+use soroban_sdk::{map, testutils::BytesN as _, vec, BytesN, Vec};
 
 use crate::{
     account::SmartAccount,
     auth::{permissions::SignerRole, proof::SignatureProofs, signer::SignerKey},
     error::Error,
     interface::SmartAccountInterface,
+    plugin::PluginInstallParams,
     tests::test_utils::{setup, Ed25519TestSigner, TestSignerTrait as _},
 };
 
@@ -26,7 +27,7 @@ fn test_revoke_admin_signer_prevented() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -58,7 +59,7 @@ fn test_revoke_standard_signer_allowed() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -86,7 +87,7 @@ fn test_add_multiple_admin_signers_success() {
         SmartAccount,
         (
             vec![&env, admin1.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -125,7 +126,7 @@ fn test_admin_count_underflow_protection() {
         SmartAccount,
         (
             vec![&env, admin1.into_signer(&env), admin2.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -170,7 +171,7 @@ fn test_has_signer_returns_true_when_signer_exists() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -202,7 +203,7 @@ fn test_has_signer_returns_false_when_signer_does_not_exist() {
         SmartAccount,
         (
             vec![&env, admin_signer.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -231,7 +232,7 @@ fn test_get_signer_returns_signer_when_exists() {
                 admin_signer.into_signer(&env),
                 standard_signer.into_signer(&env),
             ],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
@@ -265,7 +266,7 @@ fn test_get_signer_returns_error_when_signer_does_not_exist() {
         SmartAccount,
         (
             vec![&env, admin_signer.into_signer(&env)],
-            Vec::<Address>::new(&env),
+            Vec::<PluginInstallParams>::new(&env),
         ),
     );
 
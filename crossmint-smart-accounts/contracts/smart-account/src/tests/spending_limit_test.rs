@@ -0,0 +1,331 @@
+#![cfg(test)]
+
+use smart_account_interfaces::{Asset, PriceData};
+use soroban_sdk::{
+    auth::{Context, ContractContext},
+    contract, contractimpl, map, symbol_short,
+    testutils::{Address as _, BytesN as _, Ledger as _},
+    vec, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+use crate::{
+    account::SmartAccount,
+    auth::{
+        permissions::{AuthorizationCheck as _, SignerPolicy, SignerRole},
+        policy::OracleSpendingLimitPolicy,
+        proof::SignatureProofs,
+        signer::SignerKey,
+    },
+    error::Error,
+    interface::SmartAccountInterface,
+    plugin::PluginInstallParams,
+    tests::test_utils::{setup, Ed25519TestSigner, TestSignerTrait as _},
+};
+
+extern crate std;
+
+// Prices and limits use 7 decimals, like the Stellar Asset Contract
+const ONE_USD: i128 = 10_000_000;
+const ONE_TOKEN: i128 = 10_000_000;
+const START_TIME: u64 = 1_000_000;
+const PERIOD: u64 = 86_400;
+const MAX_PRICE_AGE: u64 = 300;
+
+// -----------------------------------------------------------------------------
+// Mock SEP-40 oracle
+// -----------------------------------------------------------------------------
+
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_price(env: &Env, asset: Asset, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&asset, &PriceData { price, timestamp });
+    }
+
+    pub fn lastprice(env: &Env, asset: Asset) -> Option<PriceData> {
+        env.storage().instance().get(&asset)
+    }
+}
+
+struct SpendingLimitSetup {
+    env: Env,
+    account_id: Address,
+    oracle_id: Address,
+    token: Address,
+    policy: SignerPolicy,
+    signer: Ed25519TestSigner,
+}
+
+fn setup_spending_limit(limit_usd: i128, fail_open: bool) -> SpendingLimitSetup {
+    let env = setup();
+    env.ledger().set_timestamp(START_TIME);
+
+    let oracle_id = env.register(MockOracle, ());
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    MockOracleClient::new(&env, &oracle_id).set_price(
+        &Asset::Stellar(token.clone()),
+        &(2 * ONE_USD),
+        &START_TIME,
+    );
+
+    let policy = SignerPolicy::OracleSpendingLimitPolicy(OracleSpendingLimitPolicy {
+        oracle: oracle_id.clone(),
+        limit: limit_usd * ONE_USD,
+        period: PERIOD,
+        max_price_age: MAX_PRICE_AGE,
+        fail_open,
+    });
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let signer = Ed25519TestSigner::generate(SignerRole::Standard(vec![&env, policy.clone()]));
+    let account_id = env.register(
+        SmartAccount,
+        (
+            vec![&env, admin.into_signer(&env), signer.into_signer(&env)],
+            Vec::<PluginInstallParams>::new(&env),
+        ),
+    );
+
+    SpendingLimitSetup {
+        env,
+        account_id,
+        oracle_id,
+        token,
+        policy,
+        signer,
+    }
+}
+
+fn transfer_context(env: &Env, token: &Address, amount: i128) -> Context {
+    Context::Contract(ContractContext {
+        contract: token.clone(),
+        fn_name: symbol_short!("transfer"),
+        args: (Address::generate(env), Address::generate(env), amount).into_val(env),
+    })
+}
+
+fn check_auth(
+    s: &SpendingLimitSetup,
+    signer: &Ed25519TestSigner,
+    context: Context,
+) -> Result<(), Error> {
+    let payload = BytesN::random(&s.env);
+    let (signer_key, proof) = signer.sign(&s.env, &payload);
+    let auth_payloads = SignatureProofs(map![&s.env, (signer_key, proof)]);
+    s.env
+        .try_invoke_contract_check_auth::<Error>(
+            &s.account_id,
+            &payload,
+            auth_payloads.into_val(&s.env),
+            &vec![&s.env, context],
+        )
+        .map_err(|err| match err {
+            Ok(err) => err,
+            Err(err) => panic!("{:?}", err),
+        })
+}
+
+fn check_transfer(s: &SpendingLimitSetup, amount: i128) -> Result<(), Error> {
+    check_auth(s, &s.signer, transfer_context(&s.env, &s.token, amount))
+}
+
+#[test]
+fn test_transfer_within_usd_limit_is_authorized() {
+    let s = setup_spending_limit(100, false);
+    // 40 tokens at $2 = $80
+    check_transfer(&s, 40 * ONE_TOKEN).unwrap();
+}
+
+#[test]
+fn test_transfer_above_usd_limit_is_denied() {
+    let s = setup_spending_limit(100, false);
+    // 60 tokens at $2 = $120
+    assert_eq!(
+        check_transfer(&s, 60 * ONE_TOKEN).unwrap_err(),
+        Error::InsufficientPermissions
+    );
+}
+
+#[test]
+fn test_spending_accumulates_within_period_and_resets_after() {
+    let s = setup_spending_limit(100, false);
+
+    check_transfer(&s, 30 * ONE_TOKEN).unwrap();
+    // $60 already spent, another $60 exceeds the $100 cap
+    assert_eq!(
+        check_transfer(&s, 30 * ONE_TOKEN).unwrap_err(),
+        Error::InsufficientPermissions
+    );
+    check_transfer(&s, 20 * ONE_TOKEN).unwrap();
+
+    // A new period starts with a fresh budget
+    let next_period = START_TIME + PERIOD;
+    s.env.ledger().set_timestamp(next_period);
+    MockOracleClient::new(&s.env, &s.oracle_id).set_price(
+        &Asset::Stellar(s.token.clone()),
+        &(2 * ONE_USD),
+        &next_period,
+    );
+    check_transfer(&s, 45 * ONE_TOKEN).unwrap();
+}
+
+#[test]
+fn test_stale_price_denied_when_fail_closed() {
+    let s = setup_spending_limit(100, false);
+    s.env.ledger().set_timestamp(START_TIME + MAX_PRICE_AGE + 1);
+    assert_eq!(
+        check_transfer(&s, ONE_TOKEN).unwrap_err(),
+        Error::InsufficientPermissions
+    );
+}
+
+#[test]
+fn test_stale_price_ignored_when_fail_open() {
+    let s = setup_spending_limit(100, true);
+    s.env.ledger().set_timestamp(START_TIME + MAX_PRICE_AGE + 1);
+    check_transfer(&s, 60 * ONE_TOKEN).unwrap();
+}
+
+#[test]
+fn test_missing_price_denied_when_fail_closed() {
+    let s = setup_spending_limit(100, false);
+    let other_token = s
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&s.env))
+        .address();
+
+    assert_eq!(
+        check_auth(
+            &s,
+            &s.signer,
+            transfer_context(&s.env, &other_token, ONE_TOKEN)
+        )
+        .unwrap_err(),
+        Error::InsufficientPermissions
+    );
+}
+
+#[test]
+fn test_zero_spending_limit_is_rejected() {
+    let s = setup_spending_limit(100, false);
+    s.env.mock_all_auths();
+    let SignerPolicy::OracleSpendingLimitPolicy(mut policy) = s.policy.clone() else {
+        unreachable!()
+    };
+    policy.limit = 0;
+    let signer = Ed25519TestSigner::generate(SignerRole::Standard(vec![
+        &s.env,
+        SignerPolicy::OracleSpendingLimitPolicy(policy),
+    ]));
+
+    let res = s.env.as_contract(&s.account_id, || {
+        SmartAccount::add_signer(&s.env, signer.into_signer(&s.env))
+    });
+    assert_eq!(res.unwrap_err(), Error::InvalidSpendingLimit);
+}
+
+#[test]
+fn test_signers_with_identical_policies_have_separate_budgets() {
+    let s = setup_spending_limit(100, false);
+    s.env.mock_all_auths();
+    let other = Ed25519TestSigner::generate(SignerRole::Standard(vec![&s.env, s.policy.clone()]));
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::add_signer(&s.env, other.into_signer(&s.env))
+        })
+        .unwrap();
+
+    // $80 each: the first signer's spending does not consume the other's budget
+    check_transfer(&s, 40 * ONE_TOKEN).unwrap();
+    check_auth(
+        &s,
+        &other,
+        transfer_context(&s.env, &s.token, 40 * ONE_TOKEN),
+    )
+    .unwrap();
+    assert_eq!(
+        check_transfer(&s, 40 * ONE_TOKEN).unwrap_err(),
+        Error::InsufficientPermissions
+    );
+}
+
+#[test]
+fn test_transfer_from_counts_toward_limit() {
+    let s = setup_spending_limit(100, false);
+    let transfer_from = Context::Contract(ContractContext {
+        contract: s.token.clone(),
+        fn_name: Symbol::new(&s.env, "transfer_from"),
+        args: (
+            Address::generate(&s.env),
+            Address::generate(&s.env),
+            Address::generate(&s.env),
+            40 * ONE_TOKEN,
+        )
+            .into_val(&s.env),
+    });
+    check_auth(&s, &s.signer, transfer_from).unwrap();
+    // $80 spent through the allowance, another $40 exceeds the $100 cap
+    assert_eq!(
+        check_transfer(&s, 20 * ONE_TOKEN).unwrap_err(),
+        Error::InsufficientPermissions
+    );
+}
+
+#[test]
+fn test_non_sep41_call_is_not_counted() {
+    let s = setup_spending_limit(100, false);
+    let call = Context::Contract(ContractContext {
+        contract: s.token.clone(),
+        fn_name: Symbol::new(&s.env, "transfer_muxed"),
+        args: (Address::generate(&s.env), 60 * ONE_TOKEN).into_val(&s.env),
+    });
+    check_auth(&s, &s.signer, call).unwrap();
+    // The whole $100 budget is still available
+    check_transfer(&s, 50 * ONE_TOKEN).unwrap();
+}
+
+#[test]
+fn test_spending_is_only_recorded_for_accepted_authorizations() {
+    let s = setup_spending_limit(100, false);
+    let signer_key: SignerKey = s.signer.into_signer(&s.env).into();
+    let contexts = vec![&s.env, transfer_context(&s.env, &s.token, 30 * ONE_TOKEN)];
+
+    s.env.as_contract(&s.account_id, || {
+        // Checking is free of side effects, however often it runs
+        assert!(s.policy.is_authorized(&s.env, &signer_key, &contexts));
+        assert!(s.policy.is_authorized(&s.env, &signer_key, &contexts));
+
+        s.policy
+            .on_authorized(&s.env, &signer_key, &contexts)
+            .unwrap();
+        // $60 recorded, another $60 exceeds the $100 cap
+        assert!(!s.policy.is_authorized(&s.env, &signer_key, &contexts));
+    });
+}
+
+#[test]
+fn test_revoking_the_policy_drops_the_spending_window() {
+    let s = setup_spending_limit(100, false);
+    s.env.mock_all_auths();
+    check_transfer(&s, 40 * ONE_TOKEN).unwrap();
+
+    let signer_key: SignerKey = s.signer.into_signer(&s.env).into();
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::revoke_signer(&s.env, signer_key.clone())
+        })
+        .unwrap();
+    s.env
+        .as_contract(&s.account_id, || {
+            SmartAccount::add_signer(&s.env, s.signer.into_signer(&s.env))
+        })
+        .unwrap();
+
+    // The signer starts over instead of inheriting the $80 spent before the revocation
+    check_transfer(&s, 40 * ONE_TOKEN).unwrap();
+}
@@ -0,0 +1,90 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Ledger as _},
+    vec, Address, Env, Vec,
+};
+use storage::{TtlPolicy, DAY_IN_LEDGERS};
+
+use crate::{
+    account::SmartAccount,
+    auth::{permissions::SignerRole, signer::SignerKey},
+    interface::SmartAccountInterface,
+    plugin::PluginInstallParams,
+    tests::test_utils::{setup, Ed25519TestSigner, TestSignerTrait as _},
+};
+
+fn deploy(env: &Env, admin: &Ed25519TestSigner) -> Address {
+    env.register(
+        SmartAccount,
+        (
+            vec![env, admin.into_signer(env)],
+            Vec::<PluginInstallParams>::new(env),
+        ),
+    )
+}
+
+fn signer_ttl(env: &Env, account_id: &Address, key: &SignerKey) -> u32 {
+    env.as_contract(account_id, || env.storage().persistent().get_ttl(key))
+}
+
+/// Moves the ledger forward until the signer's TTL is just below the persistent threshold
+fn age_signer(env: &Env, account_id: &Address, key: &SignerKey) {
+    // Keep the instance alive, as a real account would through its regular use
+    env.as_contract(account_id, || {
+        let ttl = 100 * DAY_IN_LEDGERS;
+        env.storage().instance().extend_ttl(ttl, ttl);
+    });
+    let remaining = signer_ttl(env, account_id, key);
+    env.ledger().with_mut(|li| {
+        li.sequence_number += remaining - TtlPolicy::PERSISTENT.threshold + 1;
+    });
+}
+
+#[test]
+fn test_signers_are_bumped_on_write() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    let key: SignerKey = admin.into_signer(&env).into();
+    assert_eq!(
+        signer_ttl(&env, &account_id, &key),
+        TtlPolicy::PERSISTENT.extend_to
+    );
+}
+
+#[test]
+fn test_extend_ttl_keeps_signers_alive() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+    let key: SignerKey = admin.into_signer(&env).into();
+
+    age_signer(&env, &account_id, &key);
+    assert!(signer_ttl(&env, &account_id, &key) < TtlPolicy::PERSISTENT.threshold);
+
+    // Requires no authorization
+    env.as_contract(&account_id, || SmartAccount::extend_ttl(&env))
+        .unwrap();
+    assert_eq!(
+        signer_ttl(&env, &account_id, &key),
+        TtlPolicy::PERSISTENT.extend_to
+    );
+}
+
+#[test]
+fn test_extend_ttl_skips_revoked_signers() {
+    let env = setup();
+    env.mock_all_auths();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    let standard = Ed25519TestSigner::generate(SignerRole::Standard(vec![&env])).into_signer(&env);
+    let standard_key: SignerKey = standard.clone().into();
+    env.as_contract(&account_id, || {
+        SmartAccount::add_signer(&env, standard).unwrap();
+        SmartAccount::revoke_signer(&env, standard_key).unwrap();
+        SmartAccount::extend_ttl(&env).unwrap();
+    });
+}
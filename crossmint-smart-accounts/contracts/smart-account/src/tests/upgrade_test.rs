@@ -0,0 +1,312 @@
+#![cfg(test)]
+
+extern crate std;
+
+use soroban_sdk::{
+    auth::{Context, ContractContext},
+    contract, contractimpl, map, symbol_short,
+    testutils::{Address as _, BytesN as _, Ledger as _},
+    vec, Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
+};
+use upgradeable::{
+    history::MAX_UPGRADE_HISTORY, timelock, PendingUpgrade, SmartAccountUpgradeableAuth as _,
+};
+
+use crate::{
+    account::SmartAccount,
+    auth::{permissions::SignerRole, proof::SignatureProofs},
+    error::Error,
+    interface::SmartAccountInterface,
+    migration::{AccountVersion, SCHEMA_VERSION},
+    plugin::PluginInstallParams,
+    tests::test_utils::{setup, Ed25519TestSigner, TestSignerTrait as _},
+};
+
+const DELAY: u64 = 7 * 86_400;
+
+fn deploy_with_timelock(env: &Env) -> Address {
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = env.register(
+        SmartAccount,
+        (
+            vec![env, admin.into_signer(env)],
+            Vec::<PluginInstallParams>::new(env),
+        ),
+    );
+    env.as_contract(&account_id, || SmartAccount::set_upgrade_delay(env, DELAY));
+    account_id
+}
+
+#[test]
+fn test_proposed_upgrade_becomes_executable_after_delay() {
+    let env = setup();
+    let account_id = deploy_with_timelock(&env);
+    let wasm_hash = BytesN::random(&env);
+
+    let pending = env.as_contract(&account_id, || {
+        SmartAccount::propose_upgrade(&env, wasm_hash.clone())
+    });
+    assert_eq!(
+        pending,
+        PendingUpgrade {
+            wasm_hash: wasm_hash.clone(),
+            executable_at: 1_000 + DELAY,
+        }
+    );
+
+    // Once the delay has elapsed the timelock lets the proposed hash through, once
+    env.ledger().set_timestamp(1_000 + DELAY);
+    env.as_contract(&account_id, || {
+        timelock::consume_upgrade(&env, &wasm_hash);
+        assert_eq!(SmartAccount::pending_upgrade(&env), None);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1102)")]
+fn test_execute_upgrade_before_delay_fails() {
+    let env = setup();
+    let account_id = deploy_with_timelock(&env);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::propose_upgrade(&env, BytesN::random(&env));
+    });
+    env.ledger().set_timestamp(1_000 + DELAY - 1);
+    env.as_contract(&account_id, || SmartAccount::execute_upgrade(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1101)")]
+fn test_direct_upgrade_honors_timelock() {
+    let env = setup();
+    let account_id = deploy_with_timelock(&env);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::upgrade(&env, BytesN::random(&env))
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1101)")]
+fn test_direct_upgrade_rejects_unproposed_hash() {
+    let env = setup();
+    let account_id = deploy_with_timelock(&env);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::propose_upgrade(&env, BytesN::random(&env));
+    });
+    env.ledger().set_timestamp(1_000 + DELAY);
+    env.as_contract(&account_id, || {
+        SmartAccount::upgrade(&env, BytesN::random(&env))
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1103)")]
+fn test_cancelled_upgrade_cannot_execute() {
+    let env = setup();
+    let account_id = deploy_with_timelock(&env);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::propose_upgrade(&env, BytesN::random(&env));
+        SmartAccount::cancel_upgrade(&env);
+        assert_eq!(SmartAccount::pending_upgrade(&env), None);
+    });
+    env.ledger().set_timestamp(1_000 + DELAY);
+    env.as_contract(&account_id, || SmartAccount::execute_upgrade(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1104)")]
+fn test_pending_upgrade_cannot_be_replaced() {
+    let env = setup();
+    let account_id = deploy_with_timelock(&env);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::propose_upgrade(&env, BytesN::random(&env));
+        SmartAccount::propose_upgrade(&env, BytesN::random(&env));
+    });
+}
+
+#[test]
+fn test_lowering_delay_waits_for_current_delay() {
+    let env = setup();
+    let account_id = deploy_with_timelock(&env);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::set_upgrade_delay(&env, 0);
+        assert_eq!(SmartAccount::upgrade_delay(&env), DELAY);
+    });
+
+    env.ledger().set_timestamp(1_000 + DELAY);
+    env.as_contract(&account_id, || {
+        assert_eq!(SmartAccount::upgrade_delay(&env), 0);
+        // Raising the delay again applies immediately
+        SmartAccount::set_upgrade_delay(&env, 2 * DELAY);
+        assert_eq!(SmartAccount::upgrade_delay(&env), 2 * DELAY);
+    });
+}
+
+// -----------------------------------------------------------------------------
+// Release registry restricting upgrades to published wasm hashes
+// -----------------------------------------------------------------------------
+
+const PUBLISHED: Symbol = symbol_short!("published");
+
+#[contract]
+pub struct MockReleaseRegistry;
+
+#[contractimpl]
+impl MockReleaseRegistry {
+    pub fn publish(env: &Env, wasm_hash: BytesN<32>) {
+        env.storage().instance().set(&(PUBLISHED, wasm_hash), &true);
+    }
+
+    pub fn is_published(env: &Env, wasm_hash: BytesN<32>) -> bool {
+        env.storage().instance().has(&(PUBLISHED, wasm_hash))
+    }
+}
+
+fn deploy_with_registry(env: &Env) -> (Address, MockReleaseRegistryClient<'static>) {
+    let account_id = deploy_with_timelock(env);
+    let registry = MockReleaseRegistryClient::new(env, &env.register(MockReleaseRegistry, ()));
+    env.as_contract(&account_id, || {
+        SmartAccount::set_upgrade_registry(env, registry.address.clone())
+    })
+    .unwrap();
+    (account_id, registry)
+}
+
+#[test]
+fn test_registry_allows_published_release() {
+    let env = setup();
+    let (account_id, registry) = deploy_with_registry(&env);
+    let wasm_hash = BytesN::random(&env);
+    registry.publish(&wasm_hash);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::propose_upgrade(&env, wasm_hash.clone());
+        assert_eq!(
+            SmartAccount::upgrade_registry(&env),
+            Some(registry.address.clone())
+        );
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #122)")]
+fn test_registry_rejects_unpublished_wasm() {
+    let env = setup();
+    let (account_id, _registry) = deploy_with_registry(&env);
+
+    env.as_contract(&account_id, || {
+        SmartAccount::propose_upgrade(&env, BytesN::random(&env));
+    });
+}
+
+#[test]
+fn test_upgrade_registry_cannot_be_replaced() {
+    let env = setup();
+    let (account_id, _registry) = deploy_with_registry(&env);
+
+    let res = env.as_contract(&account_id, || {
+        SmartAccount::set_upgrade_registry(&env, Address::generate(&env))
+    });
+    assert_eq!(res.unwrap_err(), Error::UpgradeRegistryLocked);
+}
+
+// -----------------------------------------------------------------------------
+// Version and upgrade history
+// -----------------------------------------------------------------------------
+
+fn deploy(env: &Env, admin: &Ed25519TestSigner) -> Address {
+    env.register(
+        SmartAccount,
+        (
+            vec![env, admin.into_signer(env)],
+            Vec::<PluginInstallParams>::new(env),
+        ),
+    )
+}
+
+#[test]
+fn test_version_reports_release_and_schema() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    let version = env.as_contract(&account_id, || SmartAccount::version(&env));
+    assert_eq!(
+        version,
+        AccountVersion {
+            semver: String::from_str(&env, env!("CARGO_PKG_VERSION")),
+            schema_version: SCHEMA_VERSION,
+        }
+    );
+}
+
+#[test]
+fn test_upgrade_history_links_upgrades_and_is_bounded() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    let hashes: std::vec::Vec<BytesN<32>> = (0..MAX_UPGRADE_HISTORY + 2)
+        .map(|_| BytesN::random(&env))
+        .collect();
+    env.as_contract(&account_id, || {
+        for hash in hashes.iter() {
+            upgradeable::start_upgrade(&env, hash, SmartAccount::_upgrade_authorized_by(&env));
+        }
+    });
+
+    let history = env.as_contract(&account_id, || SmartAccount::upgrade_history(&env));
+    assert_eq!(history.len(), MAX_UPGRADE_HISTORY);
+    // The two oldest upgrades were dropped
+    let oldest = history.get(0).unwrap();
+    assert_eq!(oldest.previous_wasm_hash, Some(hashes[1].clone()));
+    assert_eq!(oldest.new_wasm_hash, hashes[2]);
+    let latest = history.last().unwrap();
+    assert_eq!(latest.new_wasm_hash, *hashes.last().unwrap());
+    // Without a checked authorization, the account itself is reported
+    let account: Val = account_id.into_val(&env);
+    assert_eq!(latest.authorized_by, vec![&env, account]);
+}
+
+#[test]
+fn test_upgrade_history_records_signers_of_the_upgrade() {
+    let env = setup();
+    let admin = Ed25519TestSigner::generate(SignerRole::Admin);
+    let account_id = deploy(&env, &admin);
+
+    let payload = BytesN::random(&env);
+    let (admin_key, admin_proof) = admin.sign(&env, &payload);
+    let upgrade_context = Context::Contract(ContractContext {
+        contract: account_id.clone(),
+        fn_name: Symbol::new(&env, "upgrade"),
+        args: (BytesN::<32>::random(&env),).into_val(&env),
+    });
+    env.try_invoke_contract_check_auth::<Error>(
+        &account_id,
+        &payload,
+        SignatureProofs(map![&env, (admin_key.clone(), admin_proof)]).into_val(&env),
+        &vec![&env, upgrade_context],
+    )
+    .unwrap();
+
+    let wasm_hash = BytesN::random(&env);
+    env.as_contract(&account_id, || {
+        upgradeable::start_upgrade(&env, &wasm_hash, SmartAccount::_upgrade_authorized_by(&env));
+    });
+
+    let record = env
+        .as_contract(&account_id, || SmartAccount::upgrade_history(&env))
+        .last()
+        .unwrap();
+    let admin_key: Val = admin_key.into_val(&env);
+    assert_eq!(record.previous_wasm_hash, None);
+    assert_eq!(record.new_wasm_hash, wasm_hash);
+    assert_eq!(record.authorized_by, vec![&env, admin_key]);
+}
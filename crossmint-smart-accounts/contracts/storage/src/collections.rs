@@ -0,0 +1,367 @@
+//! Enumerable sets and maps spread over many persistent entries.
+//!
+//! A single `Map` value is bounded by the ledger entry size limit. These collections store
+//! each member in its own entries instead, plus an index of their keys split into pages of
+//! `INDEX_PAGE_SIZE`, so that they can grow without limit and still be listed page by page.
+//!
+//! Removal swaps the last key of the index into the removed key's position, so it touches
+//! a constant number of entries but does not preserve insertion order.
+use core::marker::PhantomData;
+
+use soroban_sdk::{contracttype, symbol_short, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+use crate::{storage_events, Error, Namespace, Storage, StorageEvents};
+
+/// Number of keys held by an index page.
+pub const INDEX_PAGE_SIZE: u32 = 32;
+
+const LEN: Symbol = symbol_short!("len");
+const PAGE: Symbol = symbol_short!("page");
+const POSITION: Symbol = symbol_short!("pos");
+const VALUE: Symbol = symbol_short!("val");
+
+#[contracttype]
+#[derive(Clone)]
+pub enum CollectionOperation {
+    Insert,
+    Update,
+    Remove,
+}
+
+/// Published with topics `("storage", "coll")` when a member is inserted, updated or removed.
+#[contracttype]
+#[derive(Clone)]
+pub struct CollectionChangeEvent {
+    pub namespace: Namespace,
+    pub name: Symbol,
+    pub operation: CollectionOperation,
+    pub key: Val,
+}
+
+/// Index of the keys of a collection: positions of the keys and pages of keys by position.
+struct KeyIndex<K> {
+    namespace: Namespace,
+    name: Symbol,
+    _key: PhantomData<K>,
+}
+
+impl<K> KeyIndex<K>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    fn new(namespace: Namespace, name: Symbol) -> Self {
+        Self {
+            namespace,
+            name,
+            _key: PhantomData,
+        }
+    }
+
+    /// Bookkeeping entries publish no storage events; the collection publishes its own.
+    fn storage() -> Storage {
+        Storage::persistent().with_events(StorageEvents::Disabled)
+    }
+
+    fn len_key(&self) -> (Namespace, Symbol, Symbol) {
+        (self.namespace, self.name.clone(), LEN)
+    }
+
+    fn page_key(&self, page: u32) -> (Namespace, Symbol, Symbol, u32) {
+        (self.namespace, self.name.clone(), PAGE, page)
+    }
+
+    fn position_key(&self, key: &K) -> (Namespace, Symbol, Symbol, K) {
+        (self.namespace, self.name.clone(), POSITION, key.clone())
+    }
+
+    fn len(&self, env: &Env) -> u32 {
+        Self::storage().get(env, &self.len_key()).unwrap_or(0)
+    }
+
+    fn position(&self, env: &Env, key: &K) -> Option<u32> {
+        Self::storage().get(env, &self.position_key(key))
+    }
+
+    fn page(&self, env: &Env, page: u32) -> Vec<K> {
+        Self::storage()
+            .get(env, &self.page_key(page))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Appends `key`. Returns false if it is already indexed.
+    fn insert(&self, env: &Env, key: &K) -> Result<bool, Error> {
+        if self.position(env, key).is_some() {
+            return Ok(false);
+        }
+        let len = self.len(env);
+        let page_no = len / INDEX_PAGE_SIZE;
+        let mut page = self.page(env, page_no);
+        page.push_back(key.clone());
+        upsert(env, &self.page_key(page_no), &page)?;
+        upsert(env, &self.position_key(key), &len)?;
+        upsert(env, &self.len_key(), &(len + 1))?;
+        Ok(true)
+    }
+
+    /// Removes `key` by moving the last key into its position. Returns false if it is not
+    /// indexed.
+    fn remove(&self, env: &Env, key: &K) -> Result<bool, Error> {
+        let Some(position) = self.position(env, key) else {
+            return Ok(false);
+        };
+        let storage = Self::storage();
+        let last = self.len(env) - 1;
+        let last_page_no = last / INDEX_PAGE_SIZE;
+        let mut last_page = self.page(env, last_page_no);
+        let last_key = last_page.pop_back().ok_or(Error::NotFound)?;
+
+        if position != last {
+            let page_no = position / INDEX_PAGE_SIZE;
+            let slot = position % INDEX_PAGE_SIZE;
+            if page_no == last_page_no {
+                last_page.set(slot, last_key.clone());
+            } else {
+                let mut page = self.page(env, page_no);
+                page.set(slot, last_key.clone());
+                storage.update(env, &self.page_key(page_no), &page)?;
+            }
+            storage.update(env, &self.position_key(&last_key), &position)?;
+        }
+
+        if last_page.is_empty() {
+            storage.delete(env, &self.page_key(last_page_no))?;
+        } else {
+            storage.update(env, &self.page_key(last_page_no), &last_page)?;
+        }
+        storage.delete(env, &self.position_key(key))?;
+        if last == 0 {
+            storage.delete(env, &self.len_key())?;
+        } else {
+            storage.update(env, &self.len_key(), &last)?;
+        }
+        Ok(true)
+    }
+
+    /// Returns up to `limit` keys starting at position `start`.
+    fn keys(&self, env: &Env, start: u32, limit: u32) -> Vec<K> {
+        let end = self.len(env).min(start.saturating_add(limit));
+        let mut keys = Vec::new(env);
+        let mut position = start;
+        while position < end {
+            let page = self.page(env, position / INDEX_PAGE_SIZE);
+            let first = position % INDEX_PAGE_SIZE;
+            let last = page.len().min(first + (end - position));
+            if last <= first {
+                break;
+            }
+            for slot in first..last {
+                keys.push_back(page.get_unchecked(slot));
+            }
+            position += last - first;
+        }
+        keys
+    }
+
+    /// Extends the TTL of the length and of the index pages holding positions
+    /// `start..start + limit`, and returns the keys at those positions.
+    fn extend_ttl(&self, env: &Env, start: u32, limit: u32) -> Result<Vec<K>, Error> {
+        let storage = Self::storage();
+        let len = self.len(env);
+        if len == 0 {
+            return Ok(Vec::new(env));
+        }
+        storage.extend_ttl(env, &self.len_key())?;
+        let end = len.min(start.saturating_add(limit));
+        if start < end {
+            for page_no in (start / INDEX_PAGE_SIZE)..=((end - 1) / INDEX_PAGE_SIZE) {
+                storage.extend_ttl(env, &self.page_key(page_no))?;
+            }
+        }
+        let keys = self.keys(env, start, limit);
+        for key in keys.iter() {
+            storage.extend_ttl(env, &self.position_key(&key))?;
+        }
+        Ok(keys)
+    }
+
+    fn publish(&self, env: &Env, operation: CollectionOperation, key: &K) {
+        if storage_events(env) == StorageEvents::Disabled {
+            return;
+        }
+        let event = CollectionChangeEvent {
+            namespace: self.namespace,
+            name: self.name.clone(),
+            operation,
+            key: key.clone().into_val(env),
+        };
+        env.events()
+            .publish((symbol_short!("storage"), symbol_short!("coll")), event);
+    }
+}
+
+fn upsert<K, V>(env: &Env, key: &K, value: &V) -> Result<(), Error>
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    let storage = Storage::persistent().with_events(StorageEvents::Disabled);
+    if storage.has(env, key) {
+        storage.update(env, key, value)
+    } else {
+        storage.store(env, key, value)
+    }
+}
+
+/// Enumerable set of keys in persistent storage.
+///
+/// Collections are identified by their namespace and name, e.g. a module keeps its
+/// guardians under `(Namespace::Recovery, "guardians")`.
+pub struct StorageSet<K> {
+    index: KeyIndex<K>,
+}
+
+impl<K> StorageSet<K>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    pub fn new(namespace: Namespace, name: Symbol) -> Self {
+        Self {
+            index: KeyIndex::new(namespace, name),
+        }
+    }
+
+    /// Adds `key` to the set. Returns false if it was already a member.
+    pub fn insert(&self, env: &Env, key: &K) -> Result<bool, Error> {
+        let inserted = self.index.insert(env, key)?;
+        if inserted {
+            self.index.publish(env, CollectionOperation::Insert, key);
+        }
+        Ok(inserted)
+    }
+
+    /// Removes `key` from the set. Returns false if it was not a member.
+    pub fn remove(&self, env: &Env, key: &K) -> Result<bool, Error> {
+        let removed = self.index.remove(env, key)?;
+        if removed {
+            self.index.publish(env, CollectionOperation::Remove, key);
+        }
+        Ok(removed)
+    }
+
+    pub fn contains(&self, env: &Env, key: &K) -> bool {
+        self.index.position(env, key).is_some()
+    }
+
+    pub fn len(&self, env: &Env) -> u32 {
+        self.index.len(env)
+    }
+
+    pub fn is_empty(&self, env: &Env) -> bool {
+        self.len(env) == 0
+    }
+
+    /// Returns up to `limit` members, starting at position `start`.
+    pub fn keys(&self, env: &Env, start: u32, limit: u32) -> Vec<K> {
+        self.index.keys(env, start, limit)
+    }
+
+    /// Extends the TTL of the members at positions `start..start + limit` and of the
+    /// entries needed to reach them.
+    pub fn extend_ttl(&self, env: &Env, start: u32, limit: u32) -> Result<(), Error> {
+        self.index.extend_ttl(env, start, limit)?;
+        Ok(())
+    }
+}
+
+/// Enumerable map in persistent storage, with each value in its own entry.
+pub struct StorageMap<K, V> {
+    index: KeyIndex<K>,
+    _value: PhantomData<V>,
+}
+
+impl<K, V> StorageMap<K, V>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    pub fn new(namespace: Namespace, name: Symbol) -> Self {
+        Self {
+            index: KeyIndex::new(namespace, name),
+            _value: PhantomData,
+        }
+    }
+
+    fn value_key(&self, key: &K) -> (Namespace, Symbol, Symbol, K) {
+        (
+            self.index.namespace,
+            self.index.name.clone(),
+            VALUE,
+            key.clone(),
+        )
+    }
+
+    /// Inserts or replaces the value of `key`. Returns true if the key is new.
+    pub fn set(&self, env: &Env, key: &K, value: &V) -> Result<bool, Error> {
+        let inserted = self.index.insert(env, key)?;
+        upsert(env, &self.value_key(key), value)?;
+        let operation = if inserted {
+            CollectionOperation::Insert
+        } else {
+            CollectionOperation::Update
+        };
+        self.index.publish(env, operation, key);
+        Ok(inserted)
+    }
+
+    pub fn get(&self, env: &Env, key: &K) -> Option<V> {
+        KeyIndex::<K>::storage().get(env, &self.value_key(key))
+    }
+
+    /// Removes `key` and returns its value, if it was present.
+    pub fn remove(&self, env: &Env, key: &K) -> Result<Option<V>, Error> {
+        let Some(value) = self.get(env, key) else {
+            return Ok(None);
+        };
+        self.index.remove(env, key)?;
+        KeyIndex::<K>::storage().delete(env, &self.value_key(key))?;
+        self.index.publish(env, CollectionOperation::Remove, key);
+        Ok(Some(value))
+    }
+
+    pub fn contains(&self, env: &Env, key: &K) -> bool {
+        self.index.position(env, key).is_some()
+    }
+
+    pub fn len(&self, env: &Env) -> u32 {
+        self.index.len(env)
+    }
+
+    pub fn is_empty(&self, env: &Env) -> bool {
+        self.len(env) == 0
+    }
+
+    /// Returns up to `limit` keys, starting at position `start`.
+    pub fn keys(&self, env: &Env, start: u32, limit: u32) -> Vec<K> {
+        self.index.keys(env, start, limit)
+    }
+
+    /// Returns up to `limit` entries, starting at position `start`.
+    pub fn entries(&self, env: &Env, start: u32, limit: u32) -> Vec<(K, V)> {
+        let mut entries = Vec::new(env);
+        for key in self.keys(env, start, limit).iter() {
+            if let Some(value) = self.get(env, &key) {
+                entries.push_back((key, value));
+            }
+        }
+        entries
+    }
+
+    /// Extends the TTL of the entries at positions `start..start + limit` and of the
+    /// entries needed to reach them.
+    pub fn extend_ttl(&self, env: &Env, start: u32, limit: u32) -> Result<(), Error> {
+        for key in self.index.extend_ttl(env, start, limit)?.iter() {
+            KeyIndex::<K>::storage().extend_ttl(env, &self.value_key(&key))?;
+        }
+        Ok(())
+    }
+}
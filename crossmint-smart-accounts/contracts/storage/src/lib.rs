@@ -1,6 +1,14 @@
 #![no_std]
 
-use soroban_sdk::{contracttype, symbol_short, Env, IntoVal, TryFromVal, Val};
+use soroban_sdk::{
+    contracttype, symbol_short, xdr::ToXdr, BytesN, Env, IntoVal, Symbol, TryFromVal, Val,
+};
+
+pub mod collections;
+mod store;
+
+pub use collections::{StorageMap, StorageSet};
+pub use store::{Namespace, Store};
 
 #[derive(Debug)]
 pub enum Error {
@@ -13,6 +21,8 @@ pub enum Error {
 pub enum StorageType {
     Persistent,
     Instance,
+    /// Cheaper entries that are deleted once their TTL expires, for short-lived state
+    Temporary,
 }
 
 #[contracttype]
@@ -28,30 +38,156 @@ pub enum StorageOperation {
 pub struct StorageChangeEvent {
     pub storage_type: StorageType,
     pub operation: StorageOperation,
+    pub key: Val,
+    /// SHA-256 of the XDR of the written value, with `StorageEvents::KeysAndValueHashes`.
+    /// Always `None` for deletions.
+    pub value_hash: Option<BytesN<32>>,
+}
+
+/// Which `StorageChangeEvent`s are published for writes.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum StorageEvents {
+    /// No events, to save fees
+    Disabled = 0,
+    /// Events carry the changed key
+    Keys = 1,
+    /// Events also carry a hash of the new value
+    KeysAndValueHashes = 2,
+}
+
+pub const STORAGE_EVENTS_KEY: Symbol = symbol_short!("st_events");
+
+/// Returns the contract-wide event mode, `StorageEvents::Keys` unless set otherwise.
+pub fn storage_events(env: &Env) -> StorageEvents {
+    env.storage()
+        .instance()
+        .get(&STORAGE_EVENTS_KEY)
+        .unwrap_or(StorageEvents::Keys)
+}
+
+/// Sets the event mode of every `Storage` handle that does not override it with
+/// `with_events`.
+pub fn set_storage_events(env: &Env, mode: StorageEvents) {
+    env.storage().instance().set(&STORAGE_EVENTS_KEY, &mode);
+}
+
+/// Number of ledgers closed in a day, at 5 seconds per ledger.
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// When and how far the TTL of an entry is extended.
+///
+/// Once the TTL of an entry drops below `threshold` ledgers, it is extended to `extend_to`
+/// ledgers. Entries are only extended past the threshold, so bumping on every access costs
+/// a single rent payment per `extend_to - threshold` ledgers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TtlPolicy {
+    pub threshold: u32,
+    pub extend_to: u32,
+}
+
+impl TtlPolicy {
+    /// Default for the contract instance, which also keeps the contract code alive
+    pub const INSTANCE: TtlPolicy = TtlPolicy {
+        threshold: 7 * DAY_IN_LEDGERS,
+        extend_to: 30 * DAY_IN_LEDGERS,
+    };
+    /// Default for persistent entries
+    pub const PERSISTENT: TtlPolicy = TtlPolicy {
+        threshold: 14 * DAY_IN_LEDGERS,
+        extend_to: 60 * DAY_IN_LEDGERS,
+    };
+    /// Default for temporary entries, which expire a day after they were last written
+    pub const TEMPORARY: TtlPolicy = TtlPolicy {
+        threshold: DAY_IN_LEDGERS,
+        extend_to: DAY_IN_LEDGERS,
+    };
 }
 
+impl StorageType {
+    pub fn default_ttl(&self) -> TtlPolicy {
+        match self {
+            StorageType::Persistent => TtlPolicy::PERSISTENT,
+            StorageType::Instance => TtlPolicy::INSTANCE,
+            StorageType::Temporary => TtlPolicy::TEMPORARY,
+        }
+    }
+}
+
+/// Storage of one type, emitting a `StorageChangeEvent` for every write.
+///
+/// Entries are bumped according to the TTL policy whenever they are written and, except
+/// for temporary storage, read, so that state in use is never archived. Temporary entries
+/// are meant to expire, so reads leave their TTL alone unless `with_read_bumps` enables it.
+/// The policy defaults to the storage type's `default_ttl` and can be replaced with
+/// `with_ttl`. Events follow the contract-wide `storage_events` mode unless overridden with
+/// `with_events`.
 pub struct Storage {
     storage_type: StorageType,
+    ttl: Option<TtlPolicy>,
+    bump_on_read: bool,
+    events: Option<StorageEvents>,
 }
 
 impl Default for Storage {
     fn default() -> Self {
-        Self {
-            storage_type: StorageType::Instance,
-        }
+        Self::instance()
     }
 }
 impl Storage {
     pub fn instance() -> Self {
-        Self {
-            storage_type: StorageType::Instance,
-        }
+        Self::of_type(StorageType::Instance)
     }
     pub fn persistent() -> Self {
+        Self::of_type(StorageType::Persistent)
+    }
+    pub fn temporary() -> Self {
+        Self::of_type(StorageType::Temporary)
+    }
+
+    fn of_type(storage_type: StorageType) -> Self {
         Self {
-            storage_type: StorageType::Persistent,
+            ttl: Some(storage_type.default_ttl()),
+            bump_on_read: !matches!(storage_type, StorageType::Temporary),
+            storage_type,
+            events: None,
         }
     }
+
+    /// Bumps entries according to `policy` instead of the default one.
+    pub fn with_ttl(mut self, policy: TtlPolicy) -> Self {
+        self.ttl = Some(policy);
+        self
+    }
+
+    /// Disables TTL bumps on read and write.
+    pub fn without_ttl(mut self) -> Self {
+        self.ttl = None;
+        self
+    }
+
+    /// Sets whether `get` and `has` bump the entries they find. Enabled by default, except
+    /// for temporary storage.
+    pub fn with_read_bumps(mut self, enabled: bool) -> Self {
+        self.bump_on_read = enabled;
+        self
+    }
+
+    pub fn ttl_policy(&self) -> Option<TtlPolicy> {
+        self.ttl
+    }
+
+    pub fn bumps_on_read(&self) -> bool {
+        self.bump_on_read
+    }
+
+    /// Publishes events for this handle's writes according to `mode`, ignoring the
+    /// contract-wide mode.
+    pub fn with_events(mut self, mode: StorageEvents) -> Self {
+        self.events = Some(mode);
+        self
+    }
 }
 
 impl Storage {
@@ -60,10 +196,15 @@ impl Storage {
         env: &Env,
         key: &K,
     ) -> Option<V> {
-        match self.storage_type {
+        let value = match self.storage_type {
             StorageType::Persistent => env.storage().persistent().get::<K, V>(key),
             StorageType::Instance => env.storage().instance().get::<K, V>(key),
+            StorageType::Temporary => env.storage().temporary().get::<K, V>(key),
+        };
+        if value.is_some() && self.bump_on_read {
+            self.bump(env, key);
         }
+        value
     }
 
     /// Store a value in the storage.
@@ -98,16 +239,29 @@ impl Storage {
                         }
                     })
             }
+            StorageType::Temporary => {
+                env.storage()
+                    .temporary()
+                    .try_update(key, |existing: Option<V>| {
+                        if existing.is_some() {
+                            Err(Error::AlreadyExists)
+                        } else {
+                            Ok(value.clone())
+                        }
+                    })
+            }
         };
 
         match result {
             Ok(_) => {
-                let event = StorageChangeEvent {
-                    storage_type: self.storage_type.clone(),
-                    operation: StorageOperation::Store,
-                };
-                env.events()
-                    .publish((symbol_short!("storage"), symbol_short!("store")), event);
+                self.bump(env, key);
+                self.publish_change(
+                    env,
+                    StorageOperation::Store,
+                    symbol_short!("store"),
+                    key,
+                    Some(value),
+                );
                 Ok(())
             }
             Err(e) => Err(e),
@@ -143,16 +297,29 @@ impl Storage {
                         }
                     })
             }
+            StorageType::Temporary => {
+                env.storage()
+                    .temporary()
+                    .try_update(key, |existing: Option<V>| {
+                        if existing.is_none() {
+                            Err(Error::NotFound)
+                        } else {
+                            Ok(value.clone())
+                        }
+                    })
+            }
         };
 
         match result {
             Ok(_) => {
-                let event = StorageChangeEvent {
-                    storage_type: self.storage_type.clone(),
-                    operation: StorageOperation::Update,
-                };
-                env.events()
-                    .publish((symbol_short!("storage"), symbol_short!("update")), event);
+                self.bump(env, key);
+                self.publish_change(
+                    env,
+                    StorageOperation::Update,
+                    symbol_short!("update"),
+                    key,
+                    Some(value),
+                );
                 Ok(())
             }
             Err(e) => Err(e),
@@ -173,22 +340,117 @@ impl Storage {
                 }
                 env.storage().instance().remove::<K>(key);
             }
+            StorageType::Temporary => {
+                if !env.storage().temporary().has::<K>(key) {
+                    return Err(Error::NotFound);
+                }
+                env.storage().temporary().remove::<K>(key);
+            }
         }
 
-        let event = StorageChangeEvent {
-            storage_type: self.storage_type.clone(),
-            operation: StorageOperation::Delete,
-        };
-        env.events()
-            .publish((symbol_short!("storage"), symbol_short!("delete")), event);
+        self.publish_change::<K, Val>(
+            env,
+            StorageOperation::Delete,
+            symbol_short!("delete"),
+            key,
+            None,
+        );
 
         Ok(())
     }
 
     pub fn has<K: IntoVal<Env, Val>>(&self, env: &Env, key: &K) -> bool {
-        match self.storage_type {
+        let exists = match self.storage_type {
             StorageType::Persistent => env.storage().persistent().has::<K>(key),
             StorageType::Instance => env.storage().instance().has::<K>(key),
+            StorageType::Temporary => env.storage().temporary().has::<K>(key),
+        };
+        if exists && self.bump_on_read {
+            self.bump(env, key);
+        }
+        exists
+    }
+
+    /// Extends the TTL of an entry according to the policy, or to the storage type's default
+    /// policy when bumps are disabled.
+    ///
+    /// For instance storage this extends the whole contract instance and its code.
+    pub fn extend_ttl<K: IntoVal<Env, Val>>(&self, env: &Env, key: &K) -> Result<(), Error> {
+        let policy = self.ttl.unwrap_or_else(|| self.storage_type.default_ttl());
+        match self.storage_type {
+            StorageType::Persistent => {
+                if !env.storage().persistent().has::<K>(key) {
+                    return Err(Error::NotFound);
+                }
+                env.storage()
+                    .persistent()
+                    .extend_ttl(key, policy.threshold, policy.extend_to);
+            }
+            StorageType::Instance => {
+                env.storage()
+                    .instance()
+                    .extend_ttl(policy.threshold, policy.extend_to);
+            }
+            StorageType::Temporary => {
+                if !env.storage().temporary().has::<K>(key) {
+                    return Err(Error::NotFound);
+                }
+                env.storage()
+                    .temporary()
+                    .extend_ttl(key, policy.threshold, policy.extend_to);
+            }
+        }
+        Ok(())
+    }
+
+    fn publish_change<K: IntoVal<Env, Val>, V: IntoVal<Env, Val> + Clone>(
+        &self,
+        env: &Env,
+        operation: StorageOperation,
+        verb: Symbol,
+        key: &K,
+        value: Option<&V>,
+    ) {
+        let mode = self.events.unwrap_or_else(|| storage_events(env));
+        let value_hash = match (mode, value) {
+            (StorageEvents::Disabled, _) => return,
+            (StorageEvents::KeysAndValueHashes, Some(value)) => {
+                let xdr = value.clone().to_xdr(env);
+                Some(env.crypto().sha256(&xdr).to_bytes())
+            }
+            _ => None,
+        };
+        let event = StorageChangeEvent {
+            storage_type: self.storage_type.clone(),
+            operation,
+            key: key.into_val(env),
+            value_hash,
+        };
+        env.events()
+            .publish((symbol_short!("storage"), verb), event);
+    }
+
+    /// Extends the TTL of an existing entry, if bumps are enabled.
+    fn bump<K: IntoVal<Env, Val>>(&self, env: &Env, key: &K) {
+        let Some(policy) = self.ttl else {
+            return;
+        };
+        match self.storage_type {
+            StorageType::Persistent => {
+                env.storage()
+                    .persistent()
+                    .extend_ttl(key, policy.threshold, policy.extend_to);
+            }
+            StorageType::Instance => {
+                env.storage()
+                    .instance()
+                    .extend_ttl(policy.threshold, policy.extend_to);
+            }
+            StorageType::Temporary => {
+                env.storage()
+                    .temporary()
+                    .extend_ttl(key, policy.threshold, policy.extend_to);
+            }
         }
     }
 }
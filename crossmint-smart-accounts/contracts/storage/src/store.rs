@@ -0,0 +1,92 @@
+use core::marker::PhantomData;
+
+use soroban_sdk::{contracttype, Env, IntoVal, TryFromVal, Val};
+
+use crate::{Error, Storage};
+
+/// Prefix of the keys owned by a module.
+///
+/// Every module storing state through a `Store` gets its own namespace, so that two modules
+/// can never write to the same key. New modules append a variant; existing values must not
+/// be renumbered or reused, so removed variants leave a gap.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Namespace {
+    Signers = 1,
+    Executor = 3,
+    Policies = 4,
+    Recovery = 5,
+    Upgrade = 7,
+    Factory = 8,
+}
+
+/// Typed handle on the entries of a namespace.
+///
+/// Keys are stored as `(namespace, key)` tuples, which cannot collide with keys of other
+/// namespaces, nor with the unprefixed `Symbol` and enum keys written before namespaces
+/// existed. Values are always read and written as `V`, so a key should be accessed through a
+/// single `Store` type, typically returned by a function of the owning module. A module
+/// storing several value types in one namespace keeps them under distinct key variants.
+pub struct Store<K, V> {
+    storage: Storage,
+    namespace: Namespace,
+    _types: PhantomData<(K, V)>,
+}
+
+impl<K, V> Store<K, V>
+where
+    K: IntoVal<Env, Val> + Clone,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    pub fn new(storage: Storage, namespace: Namespace) -> Self {
+        Self {
+            storage,
+            namespace,
+            _types: PhantomData,
+        }
+    }
+
+    pub fn namespace(&self) -> Namespace {
+        self.namespace
+    }
+
+    fn key(&self, key: &K) -> (Namespace, K) {
+        (self.namespace, key.clone())
+    }
+
+    pub fn get(&self, env: &Env, key: &K) -> Option<V> {
+        self.storage.get::<(Namespace, K), V>(env, &self.key(key))
+    }
+
+    /// Stores a value, failing if the key already exists.
+    pub fn store(&self, env: &Env, key: &K, value: &V) -> Result<(), Error> {
+        self.storage.store(env, &self.key(key), value)
+    }
+
+    /// Updates a value, failing if the key does not exist.
+    pub fn update(&self, env: &Env, key: &K, value: &V) -> Result<(), Error> {
+        self.storage.update(env, &self.key(key), value)
+    }
+
+    /// Stores or replaces a value.
+    pub fn set(&self, env: &Env, key: &K, value: &V) -> Result<(), Error> {
+        if self.has(env, key) {
+            self.update(env, key, value)
+        } else {
+            self.store(env, key, value)
+        }
+    }
+
+    pub fn delete(&self, env: &Env, key: &K) -> Result<(), Error> {
+        self.storage.delete(env, &self.key(key))
+    }
+
+    pub fn has(&self, env: &Env, key: &K) -> bool {
+        self.storage.has(env, &self.key(key))
+    }
+
+    pub fn extend_ttl(&self, env: &Env, key: &K) -> Result<(), Error> {
+        self.storage.extend_ttl(env, &self.key(key))
+    }
+}
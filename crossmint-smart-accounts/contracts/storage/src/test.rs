@@ -1,7 +1,16 @@
 #![cfg(test)]
 
+extern crate std;
+
 use super::*;
-use soroban_sdk::{contract, contractimpl, Address, Env, String as SorobanString, Symbol};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{
+        storage::{Instance as _, Persistent as _, Temporary as _},
+        Events as _, Ledger as _,
+    },
+    Address, BytesN, Env, String as SorobanString, Symbol, TryFromVal,
+};
 
 #[contract]
 pub struct StorageTestContract;
@@ -14,9 +23,7 @@ impl StorageTestContract {
     }
 
     pub fn store_persistent_and_check(env: Env, key: Symbol, value: SorobanString) -> bool {
-        let storage = Storage {
-            storage_type: StorageType::Persistent,
-        };
+        let storage = Storage::persistent();
         storage.store(&env, &key, &value).is_ok()
     }
 
@@ -26,9 +33,7 @@ impl StorageTestContract {
     }
 
     pub fn get_persistent_value(env: Env, key: Symbol) -> Option<SorobanString> {
-        let storage = Storage {
-            storage_type: StorageType::Persistent,
-        };
+        let storage = Storage::persistent();
         storage.get(&env, &key)
     }
 
@@ -48,9 +53,7 @@ impl StorageTestContract {
     }
 
     pub fn has_persistent_key(env: Env, key: Symbol) -> bool {
-        let storage = Storage {
-            storage_type: StorageType::Persistent,
-        };
+        let storage = Storage::persistent();
         storage.has(&env, &key)
     }
 
@@ -286,3 +289,426 @@ fn test_complete_workflow() {
     assert!(!client.has_key(&key));
     assert!(client.get_value(&key).is_none());
 }
+
+fn ttl_of(env: &Env, contract_address: &Address, key: &Symbol) -> u32 {
+    env.as_contract(contract_address, || env.storage().persistent().get_ttl(key))
+}
+
+/// Moves the ledger forward until the entry's TTL is just below `threshold`
+fn age_entry(env: &Env, contract_address: &Address, key: &Symbol, threshold: u32) {
+    // Keep the contract itself alive meanwhile
+    env.as_contract(contract_address, || {
+        let ttl = 100 * DAY_IN_LEDGERS;
+        env.storage().instance().extend_ttl(ttl, ttl);
+    });
+    let remaining = ttl_of(env, contract_address, key);
+    env.ledger()
+        .with_mut(|li| li.sequence_number += remaining - threshold + 1);
+}
+
+#[test]
+fn test_write_extends_ttl() {
+    let (env, contract_address, client) = create_test_env();
+    let key = Symbol::new(&env, "ttl_key");
+
+    assert!(client.store_persistent_and_check(&key, &SorobanString::from_str(&env, "v")));
+    assert_eq!(
+        ttl_of(&env, &contract_address, &key),
+        TtlPolicy::PERSISTENT.extend_to
+    );
+    env.as_contract(&contract_address, || {
+        assert_eq!(
+            env.storage().instance().get_ttl(),
+            TtlPolicy::INSTANCE.extend_to
+        );
+    });
+}
+
+#[test]
+fn test_read_extends_ttl_below_threshold() {
+    let (env, contract_address, client) = create_test_env();
+    let key = Symbol::new(&env, "ttl_key");
+    let policy = TtlPolicy::PERSISTENT;
+    assert!(client.store_persistent_and_check(&key, &SorobanString::from_str(&env, "v")));
+
+    age_entry(&env, &contract_address, &key, policy.threshold);
+    assert!(ttl_of(&env, &contract_address, &key) < policy.threshold);
+
+    assert!(client.get_persistent_value(&key).is_some());
+    assert_eq!(ttl_of(&env, &contract_address, &key), policy.extend_to);
+}
+
+#[test]
+fn test_custom_and_disabled_ttl_policies() {
+    let (env, contract_address, _client) = create_test_env();
+    let custom = TtlPolicy {
+        threshold: 5_000,
+        extend_to: 10_000,
+    };
+    let custom_key = Symbol::new(&env, "custom");
+    let fixed_key = Symbol::new(&env, "fixed");
+
+    env.as_contract(&contract_address, || {
+        Storage::persistent()
+            .with_ttl(custom)
+            .store(&env, &custom_key, &1u32)
+            .unwrap();
+        Storage::persistent()
+            .without_ttl()
+            .store(&env, &fixed_key, &1u32)
+            .unwrap();
+    });
+    assert_eq!(
+        ttl_of(&env, &contract_address, &custom_key),
+        custom.extend_to
+    );
+
+    // Without a policy, the entry keeps the TTL it was created with
+    let created_with = ttl_of(&env, &contract_address, &fixed_key);
+    assert!(created_with < TtlPolicy::PERSISTENT.extend_to);
+    env.as_contract(&contract_address, || {
+        Storage::persistent()
+            .without_ttl()
+            .get::<Symbol, u32>(&env, &fixed_key)
+            .unwrap();
+    });
+    assert_eq!(ttl_of(&env, &contract_address, &fixed_key), created_with);
+}
+
+#[test]
+fn test_extend_ttl_missing_entry() {
+    let (env, contract_address, _client) = create_test_env();
+
+    env.as_contract(&contract_address, || {
+        let res = Storage::persistent().extend_ttl(&env, &Symbol::new(&env, "missing"));
+        assert!(matches!(res, Err(Error::NotFound)));
+    });
+}
+
+#[test]
+fn test_temporary_storage_workflow() {
+    let (env, contract_address, _client) = create_test_env();
+    let key = Symbol::new(&env, "counter");
+
+    env.as_contract(&contract_address, || {
+        let storage = Storage::temporary();
+        assert!(matches!(
+            storage.update(&env, &key, &1u32),
+            Err(Error::NotFound)
+        ));
+        storage.store(&env, &key, &1u32).unwrap();
+        assert!(matches!(
+            storage.store(&env, &key, &1u32),
+            Err(Error::AlreadyExists)
+        ));
+        storage.update(&env, &key, &2u32).unwrap();
+        assert_eq!(storage.get::<Symbol, u32>(&env, &key), Some(2));
+
+        // Isolated from the other storage types
+        assert!(!Storage::instance().has(&env, &key));
+        assert!(!Storage::persistent().has(&env, &key));
+
+        storage.delete(&env, &key).unwrap();
+        assert!(!storage.has(&env, &key));
+    });
+}
+
+#[test]
+fn test_temporary_entries_expire() {
+    let (env, contract_address, _client) = create_test_env();
+    let key = Symbol::new(&env, "approval");
+
+    env.as_contract(&contract_address, || {
+        // Keep the contract itself alive
+        Storage::instance().extend_ttl(&env, &key).unwrap();
+        Storage::temporary().store(&env, &key, &true).unwrap();
+        assert_eq!(
+            env.storage().temporary().get_ttl(&key),
+            TtlPolicy::TEMPORARY.extend_to
+        );
+    });
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += TtlPolicy::TEMPORARY.extend_to + 1);
+    env.as_contract(&contract_address, || {
+        assert!(!Storage::temporary().has(&env, &key));
+    });
+}
+
+#[test]
+fn test_temporary_reads_do_not_extend_ttl() {
+    let (env, contract_address, _client) = create_test_env();
+    let key = Symbol::new(&env, "approval");
+    let policy = TtlPolicy::TEMPORARY;
+
+    env.as_contract(&contract_address, || {
+        Storage::instance().extend_ttl(&env, &key).unwrap();
+        Storage::temporary().store(&env, &key, &true).unwrap();
+    });
+    env.ledger().with_mut(|li| li.sequence_number += 100);
+    let aged = policy.extend_to - 100;
+
+    env.as_contract(&contract_address, || {
+        let storage = Storage::temporary();
+        assert!(!storage.bumps_on_read());
+        assert!(storage.has(&env, &key));
+        assert_eq!(storage.get::<Symbol, bool>(&env, &key), Some(true));
+        assert_eq!(env.storage().temporary().get_ttl(&key), aged);
+
+        // Opting in restores the bump on read
+        Storage::temporary()
+            .with_read_bumps(true)
+            .get::<Symbol, bool>(&env, &key)
+            .unwrap();
+        assert_eq!(env.storage().temporary().get_ttl(&key), policy.extend_to);
+    });
+}
+
+#[test]
+fn test_namespaced_stores_do_not_collide() {
+    let (env, contract_address, _client) = create_test_env();
+    let key = Symbol::new(&env, "admin_cnt");
+
+    env.as_contract(&contract_address, || {
+        let signers: Store<Symbol, u32> = Store::new(Storage::persistent(), Namespace::Signers);
+        let policies: Store<Symbol, SorobanString> =
+            Store::new(Storage::persistent(), Namespace::Policies);
+        // Unprefixed entry under the same raw key
+        Storage::persistent().store(&env, &key, &7u32).unwrap();
+
+        signers.store(&env, &key, &1).unwrap();
+        policies
+            .store(&env, &key, &SorobanString::from_str(&env, "s"))
+            .unwrap();
+
+        assert_eq!(signers.get(&env, &key), Some(1));
+        assert_eq!(
+            policies.get(&env, &key),
+            Some(SorobanString::from_str(&env, "s"))
+        );
+        assert_eq!(
+            Storage::persistent().get::<Symbol, u32>(&env, &key),
+            Some(7)
+        );
+
+        signers.delete(&env, &key).unwrap();
+        assert!(!signers.has(&env, &key));
+        assert!(policies.has(&env, &key));
+    });
+}
+
+#[test]
+fn test_store_set_upserts() {
+    let (env, contract_address, _client) = create_test_env();
+    let key = Symbol::new(&env, "nonce");
+
+    env.as_contract(&contract_address, || {
+        let store: Store<Symbol, u64> = Store::new(Storage::instance(), Namespace::Executor);
+        store.set(&env, &key, &1).unwrap();
+        store.set(&env, &key, &2).unwrap();
+        assert_eq!(store.get(&env, &key), Some(2));
+        assert!(matches!(
+            store.store(&env, &key, &3),
+            Err(Error::AlreadyExists)
+        ));
+        assert!(matches!(
+            Store::<Symbol, u64>::new(Storage::instance(), Namespace::Upgrade)
+                .update(&env, &key, &3),
+            Err(Error::NotFound)
+        ));
+    });
+}
+
+fn storage_change_events(env: &Env) -> std::vec::Vec<(Symbol, StorageChangeEvent)> {
+    env.events()
+        .all()
+        .iter()
+        .map(|(_, topics, data)| {
+            let verb = Symbol::try_from_val(env, &topics.get(1).unwrap()).unwrap();
+            (verb, StorageChangeEvent::try_from_val(env, &data).unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn test_change_events_carry_the_key() {
+    let (env, contract_address, _client) = create_test_env();
+    let key = Symbol::new(&env, "admin_cnt");
+
+    env.as_contract(&contract_address, || {
+        Storage::persistent().store(&env, &key, &1u32).unwrap();
+    });
+
+    let events = storage_change_events(&env);
+    assert_eq!(events.len(), 1);
+    let (verb, event) = &events[0];
+    assert_eq!(*verb, symbol_short!("store"));
+    assert!(matches!(event.storage_type, StorageType::Persistent));
+    assert!(matches!(event.operation, StorageOperation::Store));
+    assert_eq!(Symbol::try_from_val(&env, &event.key).unwrap(), key);
+    assert_eq!(event.value_hash, None);
+}
+
+#[test]
+fn test_change_events_with_value_hashes() {
+    let (env, contract_address, _client) = create_test_env();
+    let key = Symbol::new(&env, "plugins");
+    let value = SorobanString::from_str(&env, "value");
+
+    env.as_contract(&contract_address, || {
+        set_storage_events(&env, StorageEvents::KeysAndValueHashes);
+        Storage::instance().store(&env, &key, &value).unwrap();
+        Storage::instance().delete(&env, &key).unwrap();
+    });
+
+    let events = storage_change_events(&env);
+    assert_eq!(events.len(), 2);
+    let expected_hash: BytesN<32> = env.crypto().sha256(&value.clone().to_xdr(&env)).into();
+    assert_eq!(events[0].1.value_hash, Some(expected_hash));
+    // Deletions carry no value
+    assert_eq!(events[1].0, symbol_short!("delete"));
+    assert_eq!(events[1].1.value_hash, None);
+}
+
+#[test]
+fn test_disabled_change_events() {
+    let (env, contract_address, _client) = create_test_env();
+    let key = Symbol::new(&env, "counter");
+
+    env.as_contract(&contract_address, || {
+        set_storage_events(&env, StorageEvents::Disabled);
+        let storage = Storage::temporary();
+        storage.store(&env, &key, &1u32).unwrap();
+        storage.update(&env, &key, &2u32).unwrap();
+        storage.delete(&env, &key).unwrap();
+    });
+    assert!(storage_change_events(&env).is_empty());
+
+    // A handle can still opt back in
+    env.as_contract(&contract_address, || {
+        Storage::temporary()
+            .with_events(StorageEvents::Keys)
+            .store(&env, &key, &1u32)
+            .unwrap();
+        assert_eq!(storage_events(&env), StorageEvents::Disabled);
+    });
+    assert_eq!(storage_change_events(&env).len(), 1);
+}
+
+fn guardians(env: &Env) -> StorageSet<u32> {
+    StorageSet::new(Namespace::Recovery, Symbol::new(env, "guardians"))
+}
+
+#[test]
+fn test_storage_set_spans_index_pages() {
+    let (env, contract_address, _client) = create_test_env();
+    let count = 2 * collections::INDEX_PAGE_SIZE + 5;
+
+    env.as_contract(&contract_address, || {
+        let set = guardians(&env);
+        for member in 0..count {
+            assert!(set.insert(&env, &member).unwrap());
+        }
+        assert!(!set.insert(&env, &0).unwrap());
+        assert_eq!(set.len(&env), count);
+
+        let mut listed = std::vec::Vec::new();
+        let mut start = 0;
+        loop {
+            let page = set.keys(&env, start, 10);
+            if page.is_empty() {
+                break;
+            }
+            start += page.len();
+            listed.extend(page.iter());
+        }
+        assert_eq!(listed, (0..count).collect::<std::vec::Vec<u32>>());
+    });
+}
+
+#[test]
+fn test_storage_set_swap_remove() {
+    let (env, contract_address, _client) = create_test_env();
+    let count = collections::INDEX_PAGE_SIZE + 3;
+
+    env.as_contract(&contract_address, || {
+        let set = guardians(&env);
+        for member in 0..count {
+            set.insert(&env, &member).unwrap();
+        }
+
+        // The last member takes the removed member's position
+        assert!(set.remove(&env, &1).unwrap());
+        assert!(!set.remove(&env, &1).unwrap());
+        assert!(!set.contains(&env, &1));
+        assert_eq!(set.len(&env), count - 1);
+        assert_eq!(
+            set.keys(&env, 0, 3),
+            soroban_sdk::vec![&env, 0, count - 1, 2]
+        );
+
+        for member in 0..count {
+            set.remove(&env, &member).unwrap();
+        }
+        assert!(set.is_empty(&env));
+        assert!(set.keys(&env, 0, 10).is_empty());
+        // Removed members can be added again
+        assert!(set.insert(&env, &1).unwrap());
+        assert_eq!(set.keys(&env, 0, 10), soroban_sdk::vec![&env, 1]);
+    });
+}
+
+#[test]
+fn test_storage_map() {
+    let (env, contract_address, _client) = create_test_env();
+
+    env.as_contract(&contract_address, || {
+        let map: StorageMap<Symbol, u64> =
+            StorageMap::new(Namespace::Policies, Symbol::new(&env, "usage"));
+        let (a, b) = (Symbol::new(&env, "a"), Symbol::new(&env, "b"));
+
+        assert!(map.set(&env, &a, &1).unwrap());
+        assert!(map.set(&env, &b, &2).unwrap());
+        assert!(!map.set(&env, &a, &3).unwrap());
+        assert_eq!(map.get(&env, &a), Some(3));
+        assert_eq!(map.len(&env), 2);
+        assert_eq!(
+            map.entries(&env, 0, 10),
+            soroban_sdk::vec![&env, (a.clone(), 3), (b.clone(), 2)]
+        );
+
+        assert_eq!(map.remove(&env, &a).unwrap(), Some(3));
+        assert_eq!(map.remove(&env, &a).unwrap(), None);
+        assert_eq!(map.get(&env, &a), None);
+        assert_eq!(map.entries(&env, 0, 10), soroban_sdk::vec![&env, (b, 2)]);
+    });
+}
+
+#[test]
+fn test_collection_events_and_ttl() {
+    let (env, contract_address, _client) = create_test_env();
+
+    env.as_contract(&contract_address, || {
+        guardians(&env).insert(&env, &7).unwrap();
+    });
+    // A single collection event instead of one event per bookkeeping entry
+    let events = env.events().all();
+    assert_eq!(events.len(), 1);
+    let (_, topics, data) = events.get(0).unwrap();
+    assert_eq!(
+        Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
+        symbol_short!("coll")
+    );
+    let event = collections::CollectionChangeEvent::try_from_val(&env, &data).unwrap();
+    assert!(matches!(
+        event.operation,
+        collections::CollectionOperation::Insert
+    ));
+    assert_eq!(u32::try_from_val(&env, &event.key).unwrap(), 7);
+
+    env.as_contract(&contract_address, || {
+        guardians(&env).extend_ttl(&env, 0, 10).unwrap();
+        // Beyond the last member there is nothing to extend
+        guardians(&env).extend_ttl(&env, 5, 10).unwrap();
+    });
+}
@@ -0,0 +1,56 @@
+use soroban_sdk::{contracttype, symbol_short, BytesN, Env, Symbol, Val, Vec};
+
+pub const UPGRADE_HISTORY: Symbol = symbol_short!("UPG_HIST");
+pub const CURRENT_WASM: Symbol = symbol_short!("UPG_WASM");
+
+/// Number of upgrades kept in the history. Older records are dropped first.
+pub const MAX_UPGRADE_HISTORY: u32 = 10;
+
+/// An upgrade of the contract's wasm.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeRecord {
+    /// Wasm the contract ran before, if it was installed through a recorded upgrade.
+    /// `None` for the first upgrade, as the wasm the contract was deployed with is unknown.
+    pub previous_wasm_hash: Option<BytesN<32>>,
+    pub new_wasm_hash: BytesN<32>,
+    pub timestamp: u64,
+    /// Who authorized the upgrade, as reported by `_upgrade_authorized_by`
+    pub authorized_by: Vec<Val>,
+}
+
+/// Returns the wasm installed by the last recorded upgrade.
+pub fn current_wasm_hash(e: &Env) -> Option<BytesN<32>> {
+    e.storage().instance().get(&CURRENT_WASM)
+}
+
+/// Returns the recorded upgrades, oldest first.
+pub fn upgrade_history(e: &Env) -> Vec<UpgradeRecord> {
+    e.storage()
+        .instance()
+        .get(&UPGRADE_HISTORY)
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+/// Appends an upgrade to `new_wasm_hash` to the history, dropping the oldest record once
+/// `MAX_UPGRADE_HISTORY` is reached.
+pub fn record_upgrade(
+    e: &Env,
+    new_wasm_hash: &BytesN<32>,
+    authorized_by: Vec<Val>,
+) -> UpgradeRecord {
+    let record = UpgradeRecord {
+        previous_wasm_hash: current_wasm_hash(e),
+        new_wasm_hash: new_wasm_hash.clone(),
+        timestamp: e.ledger().timestamp(),
+        authorized_by,
+    };
+    let mut history = upgrade_history(e);
+    while history.len() >= MAX_UPGRADE_HISTORY {
+        history.pop_front();
+    }
+    history.push_back(record.clone());
+    e.storage().instance().set(&UPGRADE_HISTORY, &history);
+    e.storage().instance().set(&CURRENT_WASM, new_wasm_hash);
+    record
+}
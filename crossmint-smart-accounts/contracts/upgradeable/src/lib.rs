@@ -1,7 +1,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contracterror, panic_with_error, symbol_short, BytesN, Env, FromVal, Symbol, Val,
+    contracterror, panic_with_error, symbol_short, vec, BytesN, Env, FromVal, IntoVal, Symbol, Val,
+    Vec,
 };
 
 #[contracterror(export = false)]
@@ -9,58 +10,114 @@ use soroban_sdk::{
 #[repr(u32)]
 pub enum Error {
     MigrationNotAllowed = 1100,
+    /// A timelock is configured and the upgrade was not proposed, or proposed for another hash
+    UpgradeTimelocked = 1101,
+    /// The delay of the pending upgrade has not elapsed yet
+    UpgradeNotReady = 1102,
+    /// No upgrade is pending
+    NoPendingUpgrade = 1103,
+    /// An upgrade is already pending and must be executed or cancelled first
+    UpgradeAlreadyProposed = 1104,
 }
 
 pub const MIGRATING: Symbol = symbol_short!("MIGRATING");
 
+pub mod history;
+pub mod timelock;
+
+pub use history::UpgradeRecord;
+pub use timelock::{PendingUpgrade, UpgradeDelay};
+
 pub trait SmartAccountUpgradeable: SmartAccountUpgradeableAuth {
     fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) {
         Self::_require_auth_upgrade(env);
-        enable_migration(env);
+        start_upgrade(env, &new_wasm_hash, Self::_upgrade_authorized_by(env));
         env.deployer().update_current_contract_wasm(new_wasm_hash);
+        emit_upgrade_completed(env);
     }
 }
 
 pub trait SmartAccountUpgradeableMigratable:
     SmartAccountUpgradeableAuth + SmartAccountUpgradeableMigratableInternal
 {
+    /// Upgrades right away, unless an upgrade delay is configured. In that case the hash
+    /// must have been proposed with `propose_upgrade` and its delay must have elapsed.
     fn upgrade(e: &soroban_sdk::Env, new_wasm_hash: soroban_sdk::BytesN<32>) {
         Self::_require_auth_upgrade(e);
-        enable_migration(e);
-        e.events().publish(
-            (Symbol::new(e, "UPGRADE_STARTED"),),
-            e.current_contract_address(),
-        );
+        Self::_validate_upgrade(e, &new_wasm_hash);
+        timelock::consume_upgrade(e, &new_wasm_hash);
+        start_upgrade(e, &new_wasm_hash, Self::_upgrade_authorized_by(e));
         e.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
+    fn propose_upgrade(e: &Env, new_wasm_hash: BytesN<32>) -> PendingUpgrade {
+        Self::_require_auth_upgrade(e);
+        Self::_validate_upgrade(e, &new_wasm_hash);
+        timelock::propose_upgrade(e, new_wasm_hash)
+    }
+
+    fn cancel_upgrade(e: &Env) {
+        Self::_require_auth_upgrade(e);
+        timelock::cancel_upgrade(e);
+    }
+
+    /// Executes the pending upgrade once its delay has elapsed.
+    fn execute_upgrade(e: &Env) {
+        let Some(pending) = timelock::pending_upgrade(e) else {
+            panic_with_error!(e, Error::NoPendingUpgrade)
+        };
+        Self::upgrade(e, pending.wasm_hash);
+    }
+
+    fn set_upgrade_delay(e: &Env, delay: u64) -> UpgradeDelay {
+        Self::_require_auth_upgrade(e);
+        timelock::set_upgrade_delay(e, delay)
+    }
+
     fn migrate(e: &soroban_sdk::Env, migration_data: Self::MigrationData) {
         Self::_require_auth_upgrade(e);
         ensure_can_complete_migration(e);
         Self::_migrate(e, &migration_data);
         complete_migration(e);
-        e.events().publish(
-            (Symbol::new(e, "UPGRADE_COMPLETED"),),
-            e.current_contract_address(),
-        );
+        emit_upgrade_completed(e);
     }
 }
 
 pub trait SmartAccountUpgradeableMigratableInternal {
     type MigrationData: FromVal<Env, Val>;
     fn _migrate(e: &Env, migration_data: &Self::MigrationData);
+
+    /// Rejects upgrades to wasm the contract does not trust. Accepts any wasm by default.
+    fn _validate_upgrade(_e: &Env, _new_wasm_hash: &BytesN<32>) {}
 }
 
 pub trait SmartAccountUpgradeableAuth {
     fn _require_auth_upgrade(e: &Env);
+
+    /// Who authorized the upgrade being performed, recorded in the upgrade history.
+    /// Defaults to the contract itself.
+    fn _upgrade_authorized_by(e: &Env) -> Vec<Val> {
+        vec![e, e.current_contract_address().into_val(e)]
+    }
 }
 
 /// Macro to implement SmartAccountUpgradeable for a contract type.
 /// This generates the necessary contractimpl block with the upgrade function.
 ///
+/// The plain form emits `UPGRADE_COMPLETED` right after the wasm swap, but leaves the
+/// migrating flag set, so that new wasm using the migratable form can still run `migrate`.
+/// Such wasm must treat storage without a recorded schema version as its first schema.
+///
+/// With `migration_data = Type`, the contract implements SmartAccountUpgradeableMigratable
+/// instead and also exposes `migrate` and the upgrade timelock entrypoints. Both forms
+/// expose `upgrade_history`. The contract must implement
+/// SmartAccountUpgradeableMigratableInternal with `MigrationData = Type`, and `Type` must be
+/// in scope at the call site.
+///
 /// # Usage
 /// ```rust
 /// upgradeable::impl_upgradeable!(MyContract);
+/// upgradeable::impl_upgradeable!(MyContract, migration_data = Bytes);
 /// ```
 #[macro_export]
 macro_rules! impl_upgradeable {
@@ -69,11 +126,98 @@ macro_rules! impl_upgradeable {
         impl SmartAccountUpgradeable for $contract_type {
             fn upgrade(env: &soroban_sdk::Env, new_wasm_hash: soroban_sdk::BytesN<32>) {
                 Self::_require_auth_upgrade(env);
-                $crate::enable_migration(env);
+                $crate::start_upgrade(env, &new_wasm_hash, Self::_upgrade_authorized_by(env));
                 env.deployer().update_current_contract_wasm(new_wasm_hash);
+                $crate::emit_upgrade_completed(env);
+            }
+        }
+
+        #[soroban_sdk::contractimpl]
+        impl $contract_type {
+            pub fn upgrade_history(
+                env: &soroban_sdk::Env,
+            ) -> soroban_sdk::Vec<$crate::UpgradeRecord> {
+                $crate::history::upgrade_history(env)
             }
         }
     };
+    ($contract_type:ident, migration_data = $migration_data:ident) => {
+        impl $crate::SmartAccountUpgradeableMigratable for $contract_type {}
+
+        #[soroban_sdk::contractimpl]
+        impl $contract_type {
+            pub fn upgrade(env: &soroban_sdk::Env, new_wasm_hash: soroban_sdk::BytesN<32>) {
+                <$contract_type as $crate::SmartAccountUpgradeableMigratable>::upgrade(
+                    env,
+                    new_wasm_hash,
+                );
+            }
+
+            pub fn propose_upgrade(
+                env: &soroban_sdk::Env,
+                new_wasm_hash: soroban_sdk::BytesN<32>,
+            ) -> $crate::PendingUpgrade {
+                <$contract_type as $crate::SmartAccountUpgradeableMigratable>::propose_upgrade(
+                    env,
+                    new_wasm_hash,
+                )
+            }
+
+            pub fn cancel_upgrade(env: &soroban_sdk::Env) {
+                <$contract_type as $crate::SmartAccountUpgradeableMigratable>::cancel_upgrade(env);
+            }
+
+            pub fn execute_upgrade(env: &soroban_sdk::Env) {
+                <$contract_type as $crate::SmartAccountUpgradeableMigratable>::execute_upgrade(env);
+            }
+
+            pub fn set_upgrade_delay(env: &soroban_sdk::Env, delay: u64) -> $crate::UpgradeDelay {
+                <$contract_type as $crate::SmartAccountUpgradeableMigratable>::set_upgrade_delay(
+                    env, delay,
+                )
+            }
+
+            pub fn upgrade_delay(env: &soroban_sdk::Env) -> u64 {
+                $crate::timelock::upgrade_delay(env)
+            }
+
+            pub fn pending_upgrade(env: &soroban_sdk::Env) -> Option<$crate::PendingUpgrade> {
+                $crate::timelock::pending_upgrade(env)
+            }
+
+            pub fn upgrade_history(
+                env: &soroban_sdk::Env,
+            ) -> soroban_sdk::Vec<$crate::UpgradeRecord> {
+                $crate::history::upgrade_history(env)
+            }
+
+            pub fn migrate(env: &soroban_sdk::Env, migration_data: $migration_data) {
+                <$contract_type as $crate::SmartAccountUpgradeableMigratable>::migrate(
+                    env,
+                    migration_data,
+                );
+            }
+        }
+    };
+}
+
+/// Records the upgrade in the history, flags the contract as migrating and emits
+/// `UPGRADE_STARTED`. Called right before the wasm is replaced.
+pub fn start_upgrade(e: &Env, new_wasm_hash: &BytesN<32>, authorized_by: Vec<Val>) {
+    history::record_upgrade(e, new_wasm_hash, authorized_by);
+    enable_migration(e);
+    e.events().publish(
+        (Symbol::new(e, "UPGRADE_STARTED"),),
+        e.current_contract_address(),
+    );
+}
+
+/// Emits `UPGRADE_COMPLETED`, once the upgraded contract is ready for use.
+pub fn emit_upgrade_completed(e: &Env) {
+    e.events().publish(
+        (Symbol::new(e, "UPGRADE_COMPLETED"),),
+        e.current_contract_address(),
+    );
 }
 
 pub fn ensure_can_complete_migration(e: &Env) {
@@ -1 +1,25 @@
 #![cfg(test)]
+
+use soroban_sdk::{contract, testutils::BytesN as _, vec, BytesN, Env};
+
+use crate::{
+    can_complete_migration, emit_upgrade_completed, history::upgrade_history, start_upgrade,
+};
+
+#[contract]
+pub struct TestContract;
+
+#[test]
+fn test_completed_plain_upgrade_leaves_migration_to_the_new_wasm() {
+    let env = Env::default();
+    let contract_id = env.register(TestContract, ());
+
+    env.as_contract(&contract_id, || {
+        start_upgrade(&env, &BytesN::random(&env), vec![&env]);
+        emit_upgrade_completed(&env);
+
+        // New wasm using the migratable form can still run its migration
+        assert!(can_complete_migration(&env));
+        assert_eq!(upgrade_history(&env).len(), 1);
+    });
+}
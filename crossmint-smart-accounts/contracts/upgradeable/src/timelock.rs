@@ -0,0 +1,117 @@
+use crate::Error;
+use soroban_sdk::{contracttype, panic_with_error, symbol_short, BytesN, Env, Symbol};
+
+pub const PENDING_UPGRADE: Symbol = symbol_short!("UPG_PEND");
+pub const UPGRADE_DELAY: Symbol = symbol_short!("UPG_DELAY");
+
+/// An upgrade proposed with `propose_upgrade`, executable once the delay has elapsed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    pub wasm_hash: BytesN<32>,
+    /// Ledger timestamp from which the upgrade can be executed
+    pub executable_at: u64,
+}
+
+/// Delay between proposing and executing an upgrade.
+///
+/// Lowering the delay only takes effect once the current delay has elapsed, so a
+/// compromised admin cannot disable the timelock and upgrade right away.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeDelay {
+    pub delay: u64,
+    pub pending_delay: Option<u64>,
+    /// Ledger timestamp from which `pending_delay` applies
+    pub pending_effective_at: u64,
+}
+
+/// Returns the delay, in seconds, that currently applies to upgrades. 0 disables the timelock.
+pub fn upgrade_delay(e: &Env) -> u64 {
+    let Some(config) = e
+        .storage()
+        .instance()
+        .get::<_, UpgradeDelay>(&UPGRADE_DELAY)
+    else {
+        return 0;
+    };
+    match config.pending_delay {
+        Some(delay) if e.ledger().timestamp() >= config.pending_effective_at => delay,
+        _ => config.delay,
+    }
+}
+
+/// Sets the upgrade delay. Raising it applies immediately, lowering it once the current
+/// delay has elapsed.
+pub fn set_upgrade_delay(e: &Env, delay: u64) -> UpgradeDelay {
+    let current = upgrade_delay(e);
+    let config = if delay >= current {
+        UpgradeDelay {
+            delay,
+            pending_delay: None,
+            pending_effective_at: 0,
+        }
+    } else {
+        UpgradeDelay {
+            delay: current,
+            pending_delay: Some(delay),
+            pending_effective_at: e.ledger().timestamp().saturating_add(current),
+        }
+    };
+    e.storage().instance().set(&UPGRADE_DELAY, &config);
+    e.events()
+        .publish((Symbol::new(e, "UPGRADE_DELAY_SET"),), config.clone());
+    config
+}
+
+pub fn pending_upgrade(e: &Env) -> Option<PendingUpgrade> {
+    e.storage().instance().get(&PENDING_UPGRADE)
+}
+
+/// Records an upgrade to `wasm_hash`, executable after the current delay.
+pub fn propose_upgrade(e: &Env, wasm_hash: BytesN<32>) -> PendingUpgrade {
+    if pending_upgrade(e).is_some() {
+        panic_with_error!(e, Error::UpgradeAlreadyProposed)
+    }
+    let pending = PendingUpgrade {
+        wasm_hash,
+        executable_at: e.ledger().timestamp().saturating_add(upgrade_delay(e)),
+    };
+    e.storage().instance().set(&PENDING_UPGRADE, &pending);
+    e.events()
+        .publish((Symbol::new(e, "UPGRADE_PROPOSED"),), pending.clone());
+    pending
+}
+
+/// Discards the pending upgrade.
+pub fn cancel_upgrade(e: &Env) -> PendingUpgrade {
+    let Some(pending) = pending_upgrade(e) else {
+        panic_with_error!(e, Error::NoPendingUpgrade)
+    };
+    e.storage().instance().remove(&PENDING_UPGRADE);
+    e.events()
+        .publish((Symbol::new(e, "UPGRADE_CANCELLED"),), pending.clone());
+    pending
+}
+
+/// Checks that an upgrade to `wasm_hash` honors the timelock, and consumes the matching
+/// pending upgrade.
+///
+/// Without a delay any upgrade is allowed. With a delay, `wasm_hash` must have been
+/// proposed and its delay must have elapsed.
+pub fn consume_upgrade(e: &Env, wasm_hash: &BytesN<32>) {
+    let pending = pending_upgrade(e);
+    if upgrade_delay(e) > 0 {
+        match &pending {
+            Some(pending) if pending.wasm_hash == *wasm_hash => {
+                if e.ledger().timestamp() < pending.executable_at {
+                    panic_with_error!(e, Error::UpgradeNotReady)
+                }
+            }
+            _ => panic_with_error!(e, Error::UpgradeTimelocked),
+        }
+    }
+    if pending.is_some() {
+        e.storage().instance().remove(&PENDING_UPGRADE);
+    }
+}